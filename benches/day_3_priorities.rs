@@ -0,0 +1,204 @@
+//! Finding the item both compartments of a rucksack hold, with the unbalanced
+//! binary search tree day 3 first loaded compartments into, against the
+//! `PrioritySet` bitmask that replaced it. The balanced tree
+//! `MyBinarySearchTree` became afterwards is timed too, to tell how much of
+//! the gap was only the tree being unbalanced.
+//!
+//! Run with `cargo bench --bench day_3_priorities`.
+
+use std::{
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use aoc::day_3::{
+    priority_set::{item_of, priority_of, PrioritySet},
+    search_tree::MyBinarySearchTree,
+};
+
+const RUCKSACKS: usize = 20_000;
+const COMPARTMENT_SIZE: usize = 24;
+const RUNS: usize = 15;
+
+/// Rucksacks with `COMPARTMENT_SIZE` items in each compartment and one item in
+/// common, made up from a fixed seed so every run gets the same ones. When
+/// `sorted`, the items of each compartment come in order of priority, which
+/// the unbalanced tree turns into a linked list.
+fn rucksacks(sorted: bool) -> Vec<String> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next_priority = |range: u8| {
+        // xorshift, plenty for spreading items around
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % u64::from(range)) as u8
+    };
+
+    (0..RUCKSACKS)
+        .map(|_| {
+            // first compartment only holds items of 1..=26, the second of
+            // 27..=52, besides the one they share
+            let common = 1 + next_priority(26);
+            let mut first: Vec<u8> = (0..COMPARTMENT_SIZE - 1)
+                .map(|_| 1 + next_priority(26))
+                .chain([common])
+                .collect();
+            let mut second: Vec<u8> = (0..COMPARTMENT_SIZE - 1)
+                .map(|_| 27 + next_priority(26))
+                .chain([common])
+                .collect();
+            if sorted {
+                first.sort_unstable();
+                second.sort_unstable();
+            }
+            first
+                .into_iter()
+                .chain(second)
+                .map(|priority| item_of(priority).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// The tree as day 3 first had it, before it balanced itself, kept here as
+/// the baseline the bitmask replaced.
+mod unbalanced {
+    use aoc::day_3::priority_set::priority_of;
+
+    pub struct Tree {
+        left: Option<Box<Tree>>,
+        right: Option<Box<Tree>>,
+        data: u32,
+    }
+
+    impl Tree {
+        fn new(data: u32) -> Self {
+            Tree {
+                left: None,
+                right: None,
+                data,
+            }
+        }
+
+        fn push(&mut self, input: u32) {
+            let branch = if input < self.data {
+                &mut self.left
+            } else if input > self.data {
+                &mut self.right
+            } else {
+                return;
+            };
+
+            match branch {
+                Some(tree) => tree.push(input),
+                None => *branch = Some(Box::new(Tree::new(input))),
+            }
+        }
+
+        pub fn contains(&self, input: u32) -> bool {
+            match (&self.left, &self.right) {
+                (Some(left), _) if input < self.data => left.contains(input),
+                (_, Some(right)) if input > self.data => right.contains(input),
+                _ => input == self.data,
+            }
+        }
+
+        /// Loads the items of a compartment, `None` for an empty one or one
+        /// holding something else than items.
+        pub fn from_items(items: &str) -> Option<Self> {
+            let mut priorities = items.chars().map(|item| priority_of(item).map(u32::from));
+            let mut tree = Tree::new(priorities.next()??);
+            for priority in priorities {
+                tree.push(priority?);
+            }
+            Some(tree)
+        }
+    }
+}
+
+fn with_unbalanced_tree(rucksacks: &[String]) -> u32 {
+    let mut sum = 0;
+    for rucksack in rucksacks {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let tree = unbalanced::Tree::from_items(first).unwrap();
+        sum += second
+            .chars()
+            .map(|item| u32::from(priority_of(item).unwrap()))
+            .find(|&priority| tree.contains(priority))
+            .unwrap();
+    }
+    sum
+}
+
+fn with_search_tree(rucksacks: &[String]) -> u32 {
+    let mut sum = 0;
+    for rucksack in rucksacks {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let tree = MyBinarySearchTree::<u32>::from_str(first).unwrap();
+        sum += second
+            .chars()
+            .map(|item| u32::from(priority_of(item).unwrap()))
+            .find(|priority| tree.contains(priority))
+            .unwrap();
+    }
+    sum
+}
+
+fn with_priority_set(rucksacks: &[String]) -> u32 {
+    let mut sum = 0;
+    for rucksack in rucksacks {
+        let (first, second) = rucksack.split_at(rucksack.len() / 2);
+        let first: PrioritySet = first.parse().unwrap();
+        let second: PrioritySet = second.parse().unwrap();
+        sum += first
+            .intersection(second)
+            .iter()
+            .map(u32::from)
+            .sum::<u32>();
+    }
+    sum
+}
+
+/// Median of `RUNS` timed runs of `solve`.
+fn time(solve: impl Fn(&[String]) -> u32, rucksacks: &[String]) -> (u32, Duration) {
+    let answer = solve(rucksacks);
+    let mut samples: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(solve(black_box(rucksacks)));
+            start.elapsed()
+        })
+        .collect();
+    samples.sort();
+    (answer, samples[RUNS / 2])
+}
+
+fn main() {
+    println!(
+        "{RUCKSACKS} rucksacks of {COMPARTMENT_SIZE} items per compartment, median of {RUNS} runs"
+    );
+    println!(
+        "{:<10}{:>12}{:>12}{:>12}{:>10}",
+        "items", "unbalanced", "balanced", "bitmask", "speedup"
+    );
+    for (name, sorted) in [("shuffled", false), ("sorted", true)] {
+        let rucksacks = rucksacks(sorted);
+        let (unbalanced_answer, unbalanced_time) = time(with_unbalanced_tree, &rucksacks);
+        let (tree_answer, tree_time) = time(with_search_tree, &rucksacks);
+        let (set_answer, set_time) = time(with_priority_set, &rucksacks);
+        assert_eq!(
+            unbalanced_answer, set_answer,
+            "all should find the same items"
+        );
+        assert_eq!(tree_answer, set_answer, "all should find the same items");
+        // the improvement over what the bitmask replaced
+        println!(
+            "{name:<10}{:>12}{:>12}{:>12}{:>9.1}x",
+            format!("{unbalanced_time:.2?}"),
+            format!("{tree_time:.2?}"),
+            format!("{set_time:.2?}"),
+            unbalanced_time.as_secs_f64() / set_time.as_secs_f64()
+        );
+    }
+}
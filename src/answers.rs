@@ -0,0 +1,373 @@
+//! Known answers for my real puzzle inputs, and checking the solutions against
+//! them.
+//!
+//! Answers live in `inputs/answers.toml`, one table per day:
+//!
+//! ```toml
+//! [day_1]
+//! part_1 = "24000"
+//! part_2 = 45000
+//! ```
+//!
+//! Answers can be written as strings or integers, and a part can be left out
+//! until its answer has been accepted on the puzzle page.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    input::Origin,
+    input_cache::{InputCache, InputKind},
+    Error, Input, Part, Solver,
+};
+
+/// Where the answers of my puzzle inputs are kept.
+pub const ANSWERS_PATH: &str = "./inputs/answers.toml";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    inner: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let origin = Origin::Path(path.to_path_buf());
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            origin: origin.clone(),
+            source,
+        })?;
+        Answers::from_toml(&text).map_err(|message| Error::InvalidFile { origin, message })
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let tables: BTreeMap<String, BTreeMap<String, toml::Value>> =
+            toml::from_str(text).map_err(|error| error.to_string())?;
+
+        let mut answers = Answers::default();
+        for (day_key, parts) in tables {
+            let day = day_key
+                .strip_prefix("day_")
+                .and_then(|day| day.parse::<u8>().ok())
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| {
+                    format!("expected a table named day_1 to day_25, found {day_key}")
+                })?;
+
+            for (part_key, value) in parts {
+                let part = match part_key.as_str() {
+                    "part_1" => Part::One,
+                    "part_2" => Part::Two,
+                    _ => {
+                        return Err(format!(
+                            "expected part_1 or part_2 in {day_key}, found {part_key}"
+                        ))
+                    }
+                };
+                let answer = answer_from_value(value)
+                    .map_err(|message| format!("{message} in {day_key}.{part_key}"))?;
+                answers.insert(day, part, answer);
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.inner.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: String) {
+        self.inner.insert((day, part), answer);
+    }
+}
+
+/// Answers can be written down as strings or integers, whichever reads better.
+pub(crate) fn answer_from_value(value: toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(answer) => Ok(answer),
+        toml::Value::Integer(answer) => Ok(answer.to_string()),
+        other => Err(format!("expected a string or an integer, found {other}")),
+    }
+}
+
+/// Outcome of checking one part of a day against its stored answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// The solution ran, but there is no stored answer to check it against.
+    Unverified,
+    /// There is no puzzle input on disk, so the solution was not run.
+    NoInput,
+    /// The day has no registered solution yet.
+    NoSolution,
+    /// The solution gave up on the puzzle input with an error.
+    Failed {
+        message: String,
+    },
+}
+
+impl Verdict {
+    /// Whether the solution is known to be broken, as opposed to correct or
+    /// not checked.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. } | Verdict::Failed { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub day: u8,
+    pub part: Part,
+    /// What the solution came up with, `None` when it was not run or failed.
+    pub answer: Option<String>,
+    pub verdict: Verdict,
+    /// How long reading the input and solving it took, `None` when the
+    /// solution was not run.
+    pub elapsed: Option<Duration>,
+}
+
+/// Runs `part` of `solver` against the puzzle input stored at `path`, and
+/// checks the result against `answers`. A solution failing with an error, or
+/// panicking, is a [`Verdict::Failed`] rather than an error, so one broken day
+/// does not hide the others.
+pub fn verify(
+    solver: &dyn Solver,
+    day: u8,
+    part: Part,
+    path: &Path,
+    answers: &Answers,
+) -> Verification {
+    if !path.exists() {
+        return Verification {
+            day,
+            part,
+            answer: None,
+            verdict: Verdict::NoInput,
+            elapsed: None,
+        };
+    }
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Input::from_path(path).and_then(|input| solver.solve(part, input))
+    }));
+    let elapsed = start.elapsed();
+
+    let result = result.unwrap_or_else(|payload| {
+        // panics carry their message as a `&str` or a `String`, depending on
+        // whether it was formatted
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown reason".to_string());
+        Err(Error::Unsolvable {
+            message: format!("panicked: {message}"),
+        })
+    });
+
+    let answer = match result {
+        Ok(answer) => answer,
+        Err(error) => {
+            return Verification {
+                day,
+                part,
+                answer: None,
+                verdict: Verdict::Failed {
+                    message: error.to_string(),
+                },
+                elapsed: Some(elapsed),
+            }
+        }
+    };
+    let verdict = match answers.get(day, part) {
+        Some(expected) if expected == answer => Verdict::Correct,
+        Some(expected) => Verdict::Wrong {
+            expected: expected.to_string(),
+        },
+        None => Verdict::Unverified,
+    };
+    Verification {
+        day,
+        part,
+        answer: Some(answer),
+        verdict,
+        elapsed: Some(elapsed),
+    }
+}
+
+/// Verifies every registered day and part, or only the parts of `day`.
+pub fn verify_registered(answers: &Answers, day: Option<u8>) -> Result<Vec<Verification>, Error> {
+    let mut verifications = Vec::new();
+
+    for (registered_day, part) in crate::registered() {
+        if day.is_some_and(|day| day != registered_day) {
+            continue;
+        }
+        let solver = crate::solver(registered_day).ok_or(Error::UnknownDay(registered_day))?;
+        let path = InputCache::default().path(registered_day, &InputKind::Puzzle);
+        verifications.push(verify(solver, registered_day, part, &path, answers));
+    }
+
+    Ok(verifications)
+}
+
+/// Verifies both parts of every day of the calendar, 1 to 25, with days that
+/// have no solution yet as [`Verdict::NoSolution`].
+pub fn verify_every_day(answers: &Answers) -> Vec<Verification> {
+    (1..=25)
+        .flat_map(|day| Part::ALL.map(|part| (day, part)))
+        .map(|(day, part)| match crate::solver(day) {
+            Some(solver) => {
+                let path = InputCache::default().path(day, &InputKind::Puzzle);
+                verify(solver, day, part, &path, answers)
+            }
+            None => Verification {
+                day,
+                part,
+                answer: None,
+                verdict: Verdict::NoSolution,
+                elapsed: None,
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_string_and_integer_answers() {
+        let answers = Answers::from_toml("[day_1]\npart_1 = \"24000\"\npart_2 = 45000\n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some("24000"));
+        assert_eq!(answers.get(1, Part::Two), Some("45000"));
+        assert_eq!(answers.get(2, Part::One), None);
+    }
+
+    #[test]
+    fn should_reject_unknown_days_and_parts() {
+        assert!(Answers::from_toml("[day_26]\npart_1 = 1\n").is_err());
+        assert!(Answers::from_toml("[day_1]\npart_3 = 1\n").is_err());
+        assert!(Answers::from_toml("[day_1]\npart_1 = 1.5\n").is_err());
+    }
+
+    #[test]
+    fn should_judge_answer_against_stored_one() {
+        let solver = crate::solver(4).unwrap();
+        let path = Path::new("./inputs/day_4/mocks/example.txt");
+        let mut answers = Answers::default();
+        answers.insert(4, Part::One, "2".to_string());
+        answers.insert(4, Part::Two, "5".to_string());
+
+        let verdict = |part| verify(solver, 4, part, path, &answers).verdict;
+        assert_eq!(verdict(Part::One), Verdict::Correct);
+        assert_eq!(
+            verdict(Part::Two),
+            Verdict::Wrong {
+                expected: "5".to_string()
+            }
+        );
+
+        let missing = Path::new("./inputs/day_4/missing.txt");
+        let verification = verify(solver, 4, Part::One, missing, &answers);
+        assert_eq!(verification.verdict, Verdict::NoInput);
+    }
+
+    #[test]
+    fn should_report_solution_failing_on_its_input() {
+        let path = std::env::temp_dir().join(format!("aoc_failing_{}.txt", std::process::id()));
+        fs::write(&path, "2-4,6-8\n2-x,4-5\n").unwrap();
+        let verification = verify(
+            crate::solver(4).unwrap(),
+            4,
+            Part::One,
+            &path,
+            &Answers::default(),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(verification.answer, None);
+        assert!(verification.elapsed.is_some());
+        assert!(verification.verdict.is_failure());
+        assert!(
+            matches!(verification.verdict, Verdict::Failed { message } if message.contains("\"x\""))
+        );
+    }
+
+    struct Panicking;
+
+    impl Solver for Panicking {
+        fn part_one(&self, _input: Input) -> Result<String, Error> {
+            panic!("crate {} missing", 3)
+        }
+
+        fn part_two(&self, _input: Input) -> Result<String, Error> {
+            panic!("stack missing")
+        }
+    }
+
+    #[test]
+    fn should_report_solution_panicking_on_its_input() {
+        let path = Path::new("./inputs/day_4/mocks/example.txt");
+        let verdict = |part| verify(&Panicking, 4, part, path, &Answers::default()).verdict;
+        assert_eq!(
+            verdict(Part::One),
+            Verdict::Failed {
+                message: "panicked: crate 3 missing".to_string()
+            }
+        );
+        assert_eq!(
+            verdict(Part::Two),
+            Verdict::Failed {
+                message: "panicked: stack missing".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn should_list_every_day_of_the_calendar() {
+        let verifications = verify_every_day(&Answers::default());
+        assert_eq!(verifications.len(), 50);
+        assert_eq!(
+            (verifications[0].day, verifications[0].part),
+            (1, Part::One)
+        );
+        let last = &verifications[49];
+        assert_eq!((last.day, last.part), (25, Part::Two));
+        assert_eq!(last.verdict, Verdict::NoSolution);
+        assert!(!last.verdict.is_failure());
+    }
+
+    /// Checks the stored answer of a day and part. Days without a puzzle input
+    /// or a stored answer are skipped, so the tests pass on a fresh checkout
+    /// that has no puzzle inputs.
+    fn check_stored_answer(solver: &dyn Solver, day: u8, part: Part) {
+        let answers = match Path::new(ANSWERS_PATH).exists() {
+            true => Answers::load(Path::new(ANSWERS_PATH)).unwrap(),
+            false => Answers::default(),
+        };
+        let path = InputCache::default().path(day, &InputKind::Puzzle);
+        let verification = verify(solver, day, part, &path, &answers);
+
+        match verification.verdict {
+            Verdict::Wrong { expected } => panic!(
+                "day {day} part {part}: expected {expected}, got {}",
+                verification.answer.unwrap_or_default()
+            ),
+            Verdict::Failed { message } => panic!("day {day} part {part}: {message}"),
+            Verdict::NoInput => eprintln!("skipping day {day} part {part}: no puzzle input"),
+            Verdict::Unverified => eprintln!("skipping day {day} part {part}: no stored answer"),
+            Verdict::NoSolution | Verdict::Correct => {}
+        }
+    }
+
+    test_every_day!(check_stored_answer);
+}
@@ -0,0 +1,250 @@
+//! Timing of the registered solutions against my puzzle inputs, so the speed of
+//! a solution can be compared before and after a refactor.
+
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    input::Origin,
+    input_cache::{InputCache, InputKind},
+    Error, Input, Part, Solver,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Timed runs per day and part, at least one is always made.
+    pub iterations: u32,
+    /// Untimed runs made first, to get caches and the file system warmed up.
+    pub warm_up: u32,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iterations: 10,
+            warm_up: 2,
+        }
+    }
+}
+
+/// Fastest, median and slowest of the timed runs, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Timing {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let middle = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+        Timing {
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(median),
+            max_ns: nanos(samples[samples.len() - 1]),
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.max_ns)
+    }
+}
+
+/// How long one part of a day took: `load` reads the input into memory,
+/// `parse` is [`Solver::parse`] and `solve` is [`Solver::solve_parsed`].
+/// Solvers that parse as they go spend next to nothing in `parse`, and their
+/// parsing counts towards `solve`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub load: Timing,
+    pub parse: Timing,
+    pub solve: Timing,
+}
+
+impl Measurement {
+    /// Median solve time relative to the same day and part in `baseline`, e.g.
+    /// `0.5` when the solution got twice as fast.
+    pub fn compared_to(&self, baseline: &[Measurement]) -> Option<f64> {
+        baseline
+            .iter()
+            .find(|previous| previous.day == self.day && previous.part == self.part)
+            .filter(|previous| previous.solve.median_ns > 0)
+            .map(|previous| self.solve.median_ns as f64 / previous.solve.median_ns as f64)
+    }
+}
+
+/// Times `part` of `solver` against the input stored at `path`.
+pub fn bench_solver(
+    solver: &dyn Solver,
+    day: u8,
+    part: Part,
+    path: &Path,
+    options: BenchOptions,
+) -> Result<Measurement, Error> {
+    let iterations = options.iterations.max(1);
+    let mut load_samples = Vec::new();
+    let mut parse_samples = Vec::new();
+    let mut solve_samples = Vec::new();
+    let mut answer = String::new();
+
+    for run in 0..options.warm_up + iterations {
+        let start = Instant::now();
+        let input = Input::from_path(path)?.buffered()?;
+        let loaded = Instant::now();
+        let parsed = solver.parse(input)?;
+        let parsed_at = Instant::now();
+        answer = solver.solve_parsed(part, parsed)?;
+        let solved = Instant::now();
+
+        if run >= options.warm_up {
+            load_samples.push(loaded - start);
+            parse_samples.push(parsed_at - loaded);
+            solve_samples.push(solved - parsed_at);
+        }
+    }
+
+    Ok(Measurement {
+        day,
+        part: match part {
+            Part::One => 1,
+            Part::Two => 2,
+        },
+        answer,
+        load: Timing::from_samples(&mut load_samples),
+        parse: Timing::from_samples(&mut parse_samples),
+        solve: Timing::from_samples(&mut solve_samples),
+    })
+}
+
+/// Times every registered day and part that has a puzzle input on disk,
+/// optionally narrowed down to one `day` and/or `part`.
+pub fn bench_registered(
+    day: Option<u8>,
+    part: Option<Part>,
+    options: BenchOptions,
+) -> Result<Vec<Measurement>, Error> {
+    let mut measurements = Vec::new();
+
+    for (registered_day, registered_part) in crate::registered() {
+        if day.is_some_and(|day| day != registered_day)
+            || part.is_some_and(|part| part != registered_part)
+        {
+            continue;
+        }
+        let path = InputCache::default().path(registered_day, &InputKind::Puzzle);
+        if !path.exists() {
+            continue;
+        }
+        let solver = crate::solver(registered_day).ok_or(Error::UnknownDay(registered_day))?;
+        measurements.push(bench_solver(
+            solver,
+            registered_day,
+            registered_part,
+            &path,
+            options,
+        )?);
+    }
+
+    Ok(measurements)
+}
+
+pub fn to_json(measurements: &[Measurement]) -> String {
+    serde_json::to_string_pretty(measurements).expect("measurements are always serializable")
+}
+
+/// Stores `measurements` at `path` to compare later runs against.
+pub fn save(measurements: &[Measurement], path: &Path) -> Result<(), Error> {
+    fs::write(path, to_json(measurements)).map_err(|source| Error::Io {
+        origin: Origin::Path(path.to_path_buf()),
+        source,
+    })
+}
+
+/// Loads measurements stored by [`save`].
+pub fn load(path: &Path) -> Result<Vec<Measurement>, Error> {
+    let origin = Origin::Path(path.to_path_buf());
+    let text = fs::read_to_string(path).map_err(|source| Error::Io {
+        origin: origin.clone(),
+        source,
+    })?;
+    serde_json::from_str(&text).map_err(|error| Error::InvalidFile {
+        origin,
+        message: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_min_median_max() {
+        let mut samples: Vec<Duration> = [5, 1, 3, 2, 4].map(Duration::from_nanos).to_vec();
+        let timing = Timing::from_samples(&mut samples);
+        assert_eq!((timing.min_ns, timing.median_ns, timing.max_ns), (1, 3, 5));
+
+        let mut samples: Vec<Duration> = [4, 1, 2, 8].map(Duration::from_nanos).to_vec();
+        let timing = Timing::from_samples(&mut samples);
+        assert_eq!((timing.min_ns, timing.median_ns, timing.max_ns), (1, 3, 8));
+    }
+
+    #[test]
+    fn should_time_solver_and_keep_its_answer() {
+        let solver = crate::solver(4).unwrap();
+        let path = Path::new("./inputs/day_4/mocks/example.txt");
+        let options = BenchOptions {
+            iterations: 3,
+            warm_up: 1,
+        };
+        let measurement = bench_solver(solver, 4, Part::Two, path, options).unwrap();
+        assert_eq!((measurement.day, measurement.part), (4, 2));
+        assert_eq!(measurement.answer, "4");
+        assert!(measurement.solve.min_ns <= measurement.solve.median_ns);
+        assert!(measurement.solve.median_ns <= measurement.solve.max_ns);
+    }
+
+    #[test]
+    fn should_compare_against_same_day_and_part_of_baseline() {
+        let timing = |median_ns| Timing {
+            min_ns: median_ns,
+            median_ns,
+            max_ns: median_ns,
+        };
+        let measurement = |part, median_ns| Measurement {
+            day: 1,
+            part,
+            answer: "24000".to_string(),
+            load: timing(10),
+            parse: timing(20),
+            solve: timing(median_ns),
+        };
+        let baseline: Vec<Measurement> =
+            serde_json::from_str(&to_json(&[measurement(1, 400), measurement(2, 100)])).unwrap();
+
+        assert_eq!(measurement(1, 200).compared_to(&baseline), Some(0.5));
+        assert_eq!(measurement(2, 200).compared_to(&baseline), Some(2.0));
+        assert_eq!(measurement(2, 200).compared_to(&[]), None);
+    }
+}
@@ -1,2 +1,79 @@
 mod first_part;
+pub mod inventory;
 mod second_part;
+pub mod top_k;
+
+use crate::{mode, Error, Input, Mode, Options, Solver};
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::find_largest_calorie_held_by_an_elf(input)
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::find_sum_of_calories_carried_highest_by_three_elves(input)
+    }
+
+    fn modes(&self) -> &'static [Mode] {
+        &[
+            Mode {
+                name: "top",
+                about: "calories of the `n` elves carrying the most, and their sum (n=3)",
+            },
+            Mode {
+                name: "report",
+                about:
+                    "statistics and ranking of the elves (top=10, threshold=CALORIES, elf=INDEX)",
+            },
+        ]
+    }
+
+    fn run_mode(&self, mode: &str, options: &Options, input: Input) -> Result<String, Error> {
+        match mode {
+            "top" => {
+                let n = options.parse_or("n", 3)?;
+                let top = first_part::top_elves_by_calories(input, n)?.into_sorted_vec();
+                let mut lines: Vec<String> = top
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, calories)| format!("{}. {calories}", rank + 1))
+                    .collect();
+                lines.push(format!("total: {}", first_part::sum_calories(&top)?));
+                Ok(lines.join("\n"))
+            }
+            "report" => {
+                let inventory = inventory::ElfInventory::from_input(input)?;
+                Ok(inventory.report(
+                    options.parse_or("top", 10)?,
+                    options.parse("threshold")?,
+                    options.parse("elf")?,
+                ))
+            }
+            _ => Err(mode::unknown(mode, self.modes())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_rank_top_n_elves() {
+        let input = Input::from_path("./inputs/day_1/mocks/desc.txt").unwrap();
+        let options: Options = [("n".to_string(), "2".to_string())].into_iter().collect();
+        let report = Day1.run_mode("top", &options, input).unwrap();
+        assert_eq!(report, "1. 100\n2. 20\ntotal: 120");
+    }
+
+    #[test]
+    fn should_list_modes_on_unknown_mode() {
+        let result = Day1.run_mode("bottom", &Options::default(), Input::from_text(""));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "unknown mode \"bottom\", expected one of top, report"
+        );
+    }
+}
@@ -1,115 +1,111 @@
-use std::io::BufRead;
+use crate::{debug, input::Group, trace, Error, Input, ParseError};
 
-use crate::read_file;
+use super::top_k::TopK;
+
+/// Calories of an item or a total of them. Wide enough that only made up
+/// inputs get near its limit, and every addition is still checked.
+pub type Calories = u64;
 
 /// Working is as follows.
 ///
-/// It starts looping over the input file, and has an internal counter
-/// for an individual elf.
-///
-/// On each line iter, it will keep adding to the individual elf counter
-/// until we hit an empty line, after which it will add the total calories
-/// of the individual elf into a variable, which holds total calories of previous
-/// elf calculated. It also resets the individual elf counter to start fresh for next elf.
-///
-/// On next iter, it will do the same but the difference is that after it encounters
-/// an empty line, it'll compare the individual elf total calories with the previous
-/// elf calories, and keep the one that is the largest. This flow continues until EOF.
+/// The input comes in groups of lines separated by empty lines, one group per
+/// elf. It loops over the groups, adding up the calories of the individual
+/// elf, after which the total calories of the individual elf are handed to a
+/// [`TopK`], which only keeps the `k` largest totals seen so far.
 ///
 /// This way, we do not need to store all elf calories in an array or do any sorting on it.
 /// This was my initial plan, but the problem can be solved without needing an array.
-#[allow(dead_code)]
-fn find_largest_calorie_held_by_an_elf(filename: &str) -> String {
-    // let file = File::open(filename).unwrap();
-    // let reader = BufReader::new(file);
-    let reader = read_file(filename);
-
-    let mut previous_elf_calories_counted: Option<u32> = None;
-    let mut current_elf_calories_counter: u32 = 0;
-
-    for line in reader.lines() {
-        let line_data = line.unwrap();
-
-        if !line_data.is_empty() {
-            let calorie_entry = line_data.parse::<u32>().unwrap();
-            println!("found data {}", calorie_entry);
-            current_elf_calories_counter += calorie_entry;
-            continue;
-        }
-        println!("found empty line");
+/// Memory stays the same however long the input is, only `k` totals are kept.
+pub fn top_elves_by_calories(input: Input, k: usize) -> Result<TopK<Calories>, Error> {
+    let mut top = TopK::new(k);
+
+    for group in input.groups() {
+        let elf_calories = elf_calories(&group?)?;
+        debug!("elf done with {} calories", elf_calories);
+        top.push(elf_calories);
+    }
 
-        if previous_elf_calories_counted.is_some() {
-            if let Some(previous_elf_calories) = previous_elf_calories_counted {
-                println!(
-                    "comparing prev and new: {} - {}",
-                    previous_elf_calories, current_elf_calories_counter
-                );
-                if previous_elf_calories >= current_elf_calories_counter {
-                    current_elf_calories_counter = 0;
-                    continue;
-                }
+    Ok(top)
+}
 
-                previous_elf_calories_counted = Some(current_elf_calories_counter);
-                current_elf_calories_counter = 0;
-                continue;
-            }
-        }
+/// Total calories carried by the elf whose items are listed in `group`.
+pub(super) fn elf_calories(group: &Group) -> Result<Calories, Error> {
+    group
+        .indexed_lines()
+        .try_fold(0, |total: Calories, (line_index, line)| {
+            let calorie_entry = parse_calories(line, line_index)?;
+            trace!("found data {}", calorie_entry);
+            total
+                .checked_add(calorie_entry)
+                .ok_or_else(|| Error::Overflow {
+                    what: format!("calories of the elf at line {}", line_index + 1),
+                })
+        })
+}
 
-        previous_elf_calories_counted = Some(current_elf_calories_counter);
-        current_elf_calories_counter = 0;
-    }
+/// Reads the calories of one item, from the line at `line_index`.
+pub(super) fn parse_calories(line: &str, line_index: usize) -> Result<Calories, ParseError> {
+    line.parse::<Calories>()
+        .map_err(|_| ParseError::new(line, "a calorie count").on_line(line_index + 1))
+}
 
-    // if last line is not empty, our custom logic does not run, so repeating here
-    if let Some(previous_elf_calories) = previous_elf_calories_counted {
-        println!(
-            "comparing prev and new: {} - {}",
-            previous_elf_calories, current_elf_calories_counter
-        );
-        if previous_elf_calories < current_elf_calories_counter {
-            previous_elf_calories_counted = Some(current_elf_calories_counter);
-        }
-    }
+/// Adds up `totals` of elves, failing rather than wrapping around.
+pub(super) fn sum_calories(totals: &[Calories]) -> Result<Calories, Error> {
+    totals
+        .iter()
+        .try_fold(0, |sum: Calories, total| sum.checked_add(*total))
+        .ok_or_else(|| Error::Overflow {
+            what: format!("sum of calories of the top {} elves", totals.len()),
+        })
+}
 
-    previous_elf_calories_counted.unwrap().to_string()
+pub fn find_largest_calorie_held_by_an_elf(input: Input) -> Result<String, Error> {
+    let top = top_elves_by_calories(input, 1)?;
+    Ok(sum_calories(&top.into_sorted_vec())?.to_string())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::{Error, Input};
 
-    #[test]
-    fn should_find_largest_if_input_in_ascending() {
-        let file_path = "./inputs/day_1/mocks/asc.txt";
-        let result = find_largest_calorie_held_by_an_elf(file_path);
-        assert_eq!(result, "50");
-    }
+    use super::*;
 
     #[test]
-    fn should_find_largest_if_input_in_descending() {
-        let file_path = "./inputs/day_1/mocks/desc.txt";
-        let result = find_largest_calorie_held_by_an_elf(file_path);
-        assert_eq!(result, "100");
+    fn should_report_line_that_is_not_a_number() {
+        let input = Input::from_text("1000\n\n20x0\n");
+        match find_largest_calorie_held_by_an_elf(input) {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (3, 1));
+                assert_eq!(error.token, "20x0");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
     }
 
     #[test]
-    fn should_find_largest_if_input_in_ascending_with_empty_line_at_end() {
-        let file_path = "./inputs/day_1/mocks/asc_end_empty_line.txt";
-        let result = find_largest_calorie_held_by_an_elf(file_path);
-        assert_eq!(result, "50");
+    fn should_fail_on_elf_overflowing_calories() {
+        let input = Input::from_text("1\n\n18446744073709551615\n1\n");
+        match find_largest_calorie_held_by_an_elf(input) {
+            Err(error @ Error::Overflow { .. }) => {
+                assert_eq!(
+                    error.to_string(),
+                    "calories of the elf at line 4 overflowed"
+                )
+            }
+            other => panic!("expected an overflow, got {other:?}"),
+        }
     }
 
     #[test]
-    fn should_find_largest_if_input_in_descending_with_empty_line_at_end() {
-        let file_path = "./inputs/day_1/mocks/desc_end_empty_line.txt";
-        let result = find_largest_calorie_held_by_an_elf(file_path);
-        assert_eq!(result, "100");
+    fn should_go_past_u32_calories() {
+        let input = Input::from_text("4294967295\n1\n");
+        let result = find_largest_calorie_held_by_an_elf(input).unwrap();
+        assert_eq!(result, "4294967296");
     }
 
     #[test]
-    #[ignore]
-    fn the_actual_test() {
-        let file_path = "./inputs/day_1/aoc_input.txt";
-        let result = find_largest_calorie_held_by_an_elf(file_path);
-        assert_eq!(result, "100");
+    fn should_not_count_extra_empty_lines_as_elves() {
+        let top = top_elves_by_calories(Input::from_text("\n5\n\n\n3\n\n"), 3).unwrap();
+        assert_eq!(top.into_sorted_vec(), vec![5, 3]);
     }
 }
@@ -0,0 +1,265 @@
+use std::{cmp::Reverse, fmt::Write};
+
+use crate::{input::Group, trace, Error, Input};
+
+use super::first_part::{elf_calories, parse_calories, Calories};
+
+/// One elf and the calories of every item they carry, in input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// 1-based position of the elf in the input.
+    pub index: usize,
+    pub items: Vec<Calories>,
+    total: Calories,
+}
+
+impl Elf {
+    /// The elf at the 1-based `index`, carrying the items listed in `group`.
+    fn from_group(index: usize, group: &Group) -> Result<Self, Error> {
+        let items = group
+            .indexed_lines()
+            .map(|(line_index, line)| parse_calories(line, line_index))
+            .collect::<Result<Vec<Calories>, _>>()?;
+        trace!("elf {} carries {:?}", index, items);
+        let total = elf_calories(group)?;
+        Ok(Elf {
+            index,
+            items,
+            total,
+        })
+    }
+
+    pub fn total(&self) -> Calories {
+        self.total
+    }
+}
+
+/// Every elf of the input, kept around for exploring the data rather than
+/// only answering the puzzle, which the streaming parts do without it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ElfInventory {
+    elves: Vec<Elf>,
+}
+
+impl ElfInventory {
+    /// Reads the elves from the blank-line separated input. Runs of blank
+    /// lines do not make empty elves.
+    pub fn from_input(input: Input) -> Result<Self, Error> {
+        let mut elves = Vec::new();
+
+        for group in input.groups() {
+            elves.push(Elf::from_group(elves.len() + 1, &group?)?);
+        }
+
+        Ok(ElfInventory { elves })
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The elf at the 1-based `index`.
+    pub fn elf(&self, index: usize) -> Option<&Elf> {
+        index.checked_sub(1).and_then(|index| self.elves.get(index))
+    }
+
+    /// Elves by total calories, most first. Elves tied on calories keep their
+    /// input order.
+    pub fn ranking(&self) -> Vec<&Elf> {
+        let mut ranking: Vec<&Elf> = self.elves.iter().collect();
+        ranking.sort_by_key(|elf| Reverse(elf.total()));
+        ranking
+    }
+
+    /// Elves carrying more than `threshold` calories, in input order.
+    pub fn above(&self, threshold: Calories) -> Vec<&Elf> {
+        self.elves
+            .iter()
+            .filter(|elf| elf.total() > threshold)
+            .collect()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.elves.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.elves.iter().map(|elf| elf.total() as f64).sum();
+        Some(sum / self.elves.len() as f64)
+    }
+
+    /// Middle of the totals, or the mean of the two middle ones for an even
+    /// number of elves.
+    pub fn median(&self) -> Option<f64> {
+        let totals = self.sorted_totals();
+        let middle = totals.len() / 2;
+        match totals.len() {
+            0 => None,
+            length if length % 2 == 0 => {
+                Some((totals[middle - 1] as f64 + totals[middle] as f64) / 2.0)
+            }
+            _ => Some(totals[middle] as f64),
+        }
+    }
+
+    /// Smallest total that at least `percent` percent of the elves are at or
+    /// under, by the nearest-rank method.
+    pub fn percentile(&self, percent: f64) -> Option<Calories> {
+        let totals = self.sorted_totals();
+        if totals.is_empty() || !(0.0..=100.0).contains(&percent) {
+            return None;
+        }
+        let rank = (percent / 100.0 * totals.len() as f64).ceil() as usize;
+        Some(totals[rank.clamp(1, totals.len()) - 1])
+    }
+
+    fn sorted_totals(&self) -> Vec<Calories> {
+        let mut totals: Vec<Calories> = self.elves.iter().map(Elf::total).collect();
+        totals.sort_unstable();
+        totals
+    }
+
+    /// Text report of the inventory: statistics, the `top` elves of the
+    /// ranking, and optionally the elves above `threshold` and the items of
+    /// elf `elf`.
+    pub fn report(&self, top: usize, threshold: Option<Calories>, elf: Option<usize>) -> String {
+        let mut report = String::new();
+        let items: usize = self.elves.iter().map(|elf| elf.items.len()).sum();
+        // widened, as the calories of every elf together can go past what one
+        // elf can carry
+        let total: u128 = self.elves.iter().map(|elf| u128::from(elf.total())).sum();
+        writeln!(
+            report,
+            "elves: {}, items: {items}, calories: {total}",
+            self.elves.len()
+        )
+        .expect("writing to a string does not fail");
+
+        if let (Some(mean), Some(median)) = (self.mean(), self.median()) {
+            writeln!(report, "mean: {mean:.1}, median: {median:.1}")
+                .expect("writing to a string does not fail");
+            let percentiles: Vec<String> = [10.0, 25.0, 50.0, 75.0, 90.0]
+                .iter()
+                .filter_map(|&percent| {
+                    self.percentile(percent)
+                        .map(|total| format!("p{percent}: {total}"))
+                })
+                .collect();
+            writeln!(report, "{}", percentiles.join(", "))
+                .expect("writing to a string does not fail");
+        }
+
+        writeln!(report, "ranking:").expect("writing to a string does not fail");
+        for (rank, elf) in self.ranking().into_iter().take(top).enumerate() {
+            writeln!(
+                report,
+                "{:>4}. elf {}: {} ({} items)",
+                rank + 1,
+                elf.index,
+                elf.total(),
+                elf.items.len()
+            )
+            .expect("writing to a string does not fail");
+        }
+
+        if let Some(threshold) = threshold {
+            let above: Vec<String> = self
+                .above(threshold)
+                .iter()
+                .map(|elf| elf.index.to_string())
+                .collect();
+            writeln!(
+                report,
+                "{} elves above {threshold}: {}",
+                above.len(),
+                above.join(", ")
+            )
+            .expect("writing to a string does not fail");
+        }
+
+        if let Some(index) = elf {
+            match self.elf(index) {
+                Some(elf) => {
+                    let items: Vec<String> = elf.items.iter().map(Calories::to_string).collect();
+                    writeln!(report, "elf {index} carries {}", items.join(", "))
+                        .expect("writing to a string does not fail");
+                }
+                None => {
+                    writeln!(report, "there is no elf {index}")
+                        .expect("writing to a string does not fail");
+                }
+            }
+        }
+
+        report.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> ElfInventory {
+        let input = Input::from_text(
+            "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+        );
+        ElfInventory::from_input(input).unwrap()
+    }
+
+    #[test]
+    fn should_keep_items_of_every_elf() {
+        let inventory = example();
+        assert_eq!(inventory.elves().len(), 5);
+        assert_eq!(inventory.elf(4).unwrap().items, vec![7000, 8000, 9000]);
+        assert_eq!(inventory.elf(0), None);
+        assert_eq!(inventory.elf(6), None);
+    }
+
+    #[test]
+    fn should_report_overflowing_elf_with_its_line() {
+        let input = Input::from_text("1\n\n18446744073709551615\n1\n");
+        let error = ElfInventory::from_input(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "calories of the elf at line 4 overflowed"
+        );
+    }
+
+    #[test]
+    fn should_rank_elves_by_total() {
+        let inventory = example();
+        let ranking: Vec<usize> = inventory.ranking().iter().map(|elf| elf.index).collect();
+        assert_eq!(ranking, vec![4, 3, 5, 1, 2]);
+        let above: Vec<usize> = inventory.above(10000).iter().map(|elf| elf.index).collect();
+        assert_eq!(above, vec![3, 4]);
+    }
+
+    #[test]
+    fn should_compute_statistics() {
+        let inventory = example();
+        assert_eq!(inventory.mean(), Some(11000.0));
+        assert_eq!(inventory.median(), Some(10000.0));
+        assert_eq!(inventory.percentile(0.0), Some(4000));
+        assert_eq!(inventory.percentile(50.0), Some(10000));
+        assert_eq!(inventory.percentile(100.0), Some(24000));
+        assert_eq!(inventory.percentile(101.0), None);
+
+        let empty = ElfInventory::from_input(Input::from_text("\n\n")).unwrap();
+        assert_eq!((empty.mean(), empty.median()), (None, None));
+    }
+
+    #[test]
+    fn should_write_report() {
+        let report = example().report(2, Some(10000), Some(2));
+        assert_eq!(
+            report,
+            "elves: 5, items: 10, calories: 55000\n\
+             mean: 11000.0, median: 10000.0\n\
+             p10: 4000, p25: 6000, p50: 10000, p75: 11000, p90: 24000\n\
+             ranking:\n\
+             \x20  1. elf 4: 24000 (3 items)\n\
+             \x20  2. elf 3: 11000 (2 items)\n\
+             2 elves above 10000: 3, 4\n\
+             elf 2 carries 4000"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Keeps the `k` largest values pushed into it, without holding on to the
+/// rest, so the top of a stream is found in `O(k)` memory.
+///
+/// Equal values each take a slot, so the top three of `[5, 5, 5, 1]` are
+/// `[5, 5, 5]`. Fewer than `k` values simply leave the rest of the slots
+/// empty.
+#[derive(Debug, Clone)]
+pub struct TopK<T: Ord> {
+    k: usize,
+    // min-heap, so the smallest of the kept values is the one to evict
+    heap: BinaryHeap<Reverse<T>>,
+}
+
+/// Slots reserved up front at most, so a huge `k` asked for on the command
+/// line grows the heap as values come instead of allocating it all at once.
+const MAX_INITIAL_CAPACITY: usize = 1024;
+
+impl<T: Ord> TopK<T> {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k.min(MAX_INITIAL_CAPACITY)),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(value));
+            return;
+        }
+        if let Some(mut smallest) = self.heap.peek_mut() {
+            if value > smallest.0 {
+                *smallest = Reverse(value);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The kept values, largest first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // sorted ascending by `Reverse`, so largest first
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(value)| value)
+            .collect()
+    }
+}
+
+impl<T: Ord> Extend<T> for TopK<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TopK;
+
+    fn top(k: usize, values: &[u32]) -> Vec<u32> {
+        let mut top = TopK::new(k);
+        top.extend(values.iter().copied());
+        top.into_sorted_vec()
+    }
+
+    #[test]
+    fn should_keep_largest_values_in_any_order() {
+        assert_eq!(top(3, &[1, 9, 4, 7, 3, 8]), vec![9, 8, 7]);
+        assert_eq!(top(3, &[9, 8, 7, 6, 5]), vec![9, 8, 7]);
+        assert_eq!(top(3, &[5, 6, 7, 8, 9]), vec![9, 8, 7]);
+        assert_eq!(top(1, &[4, 10, 2]), vec![10]);
+    }
+
+    #[test]
+    fn should_keep_ties() {
+        assert_eq!(top(3, &[5, 5, 1, 5]), vec![5, 5, 5]);
+        assert_eq!(top(3, &[2, 7, 7, 3]), vec![7, 7, 3]);
+    }
+
+    #[test]
+    fn should_hold_fewer_than_k_values() {
+        assert_eq!(top(3, &[4, 2]), vec![4, 2]);
+        assert_eq!(top(3, &[]), Vec::<u32>::new());
+        assert_eq!(top(0, &[1, 2, 3]), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn should_take_any_k_without_allocating_it() {
+        assert_eq!(top(usize::MAX, &[4, 9, 2]), vec![9, 4, 2]);
+    }
+}
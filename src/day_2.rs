@@ -1,2 +1,120 @@
+pub mod cipher;
 mod first_part;
+pub mod model;
+pub mod rules;
 mod second_part;
+pub mod simulate;
+
+use crate::{mode, Error, Input, Mode, Options, Part, Solver};
+
+use cipher::Cipher;
+use rules::Rules;
+use simulate::Format;
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::get_score_according_to_strategy_guide(input)
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::get_score_according_to_strategy_guide(input)
+    }
+
+    fn modes(&self) -> &'static [Mode] {
+        &[
+            Mode {
+                name: "play",
+                about: "score the guide under other rules (rules=rps|rpsls|PATH, part=1|2)",
+            },
+            Mode {
+                name: "ciphers",
+                about: "score the guide under every reading of its second column (rules=rps)",
+            },
+            Mode {
+                name: "simulate",
+                about: "round by round transcript of the puzzle's game (part=1|2, format=text|csv)",
+            },
+        ]
+    }
+
+    fn run_mode(&self, mode: &str, options: &Options, input: Input) -> Result<String, Error> {
+        match mode {
+            "play" => {
+                let rules = Rules::named(options.get("rules").unwrap_or("rps"))?;
+                let cipher = cipher_of_part(&rules, options)?;
+                Ok(rules::play(input, &rules, &cipher)?.to_string())
+            }
+            "simulate" => {
+                let cipher = cipher_of_part(&Rules::rock_paper_scissors(), options)?;
+                let format = options.parse_or("format", Format::Text)?;
+                simulate::transcript(model::rounds(input, &cipher), format)
+            }
+            "ciphers" => {
+                let rules = Rules::named(options.get("rules").unwrap_or("rps"))?;
+                let mut ciphers =
+                    Cipher::move_permutations(&rules).map_err(|message| Error::InvalidOption {
+                        name: "rules".to_string(),
+                        message,
+                    })?;
+                ciphers.push(Cipher::outcomes());
+
+                // every cipher reads the whole guide, so it is read only once
+                let guide = input.read_to_string()?;
+                let mut scores = Vec::new();
+                for cipher in ciphers {
+                    let score = rules::play(Input::from_text(&guide), &rules, &cipher)?;
+                    scores.push((score, cipher.describe(&rules)));
+                }
+                scores.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+                let lines: Vec<String> = scores
+                    .iter()
+                    .map(|(score, cipher)| format!("{score:>8}  {cipher}"))
+                    .collect();
+                Ok(lines.join("\n"))
+            }
+            _ => Err(mode::unknown(mode, self.modes())),
+        }
+    }
+}
+
+/// The reading of the second column the part in the `part` option goes by.
+fn cipher_of_part(rules: &Rules, options: &Options) -> Result<Cipher, Error> {
+    Ok(match options.parse_or("part", Part::One)? {
+        Part::One => Cipher::moves_in_order(rules),
+        Part::Two => Cipher::outcomes(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_score_guide_under_every_cipher() {
+        let input = Input::from_path("./inputs/day_2/mocks/example.txt").unwrap();
+        let report = Day2
+            .run_mode("ciphers", &Options::default(), input)
+            .unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "      24  X=scissors, Y=paper, Z=rock");
+        assert!(lines.contains(&"      15  X=rock, Y=paper, Z=scissors"));
+        assert!(lines.contains(&"      12  X=loss, Y=draw, Z=win"));
+    }
+
+    #[test]
+    fn should_simulate_part_two() {
+        let input = Input::from_text("A X\nC Z\n");
+        let options: Options = [("part".to_string(), "2".to_string())]
+            .into_iter()
+            .collect();
+        let transcript = Day2.run_mode("simulate", &options, input).unwrap();
+        assert_eq!(
+            transcript.lines().last(),
+            Some("wins: 1, draws: 0, losses: 1, score: 10")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! How the second column of the strategy guide is encrypted.
+//!
+//! The puzzle never says what `X`, `Y` and `Z` stand for. The first part
+//! guesses they are moves, the second that they are outcomes, and a
+//! [`Cipher`] is one such guess.
+
+use crate::ParseError;
+
+use super::rules::{Move, Outcome, Rules};
+
+/// What a letter of the second column stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Meaning {
+    /// The move to play.
+    Move(Move),
+    /// The outcome to get, whichever move that takes.
+    Outcome(Outcome),
+}
+
+/// Letters of the second column and what each of them stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cipher {
+    letters: Vec<(String, Meaning)>,
+}
+
+/// Enumerating every way to read the letters as moves takes `n!` ciphers, so
+/// it stops at games this big.
+pub const MAX_PERMUTED_MOVES: usize = 8;
+
+impl Cipher {
+    pub fn new(letters: Vec<(String, Meaning)>) -> Result<Self, String> {
+        for (index, (letter, _)) in letters.iter().enumerate() {
+            if letters[..index].iter().any(|(known, _)| known == letter) {
+                return Err(format!("letter {letter} stands for two things"));
+            }
+        }
+        Ok(Cipher { letters })
+    }
+
+    /// The first part's reading: as many letters as there are moves, ending in
+    /// `Z`, standing for the moves in order. `X`, `Y`, `Z` are rock, paper and
+    /// scissors in the puzzle's game.
+    pub fn moves_in_order(rules: &Rules) -> Self {
+        let moves: Vec<Move> = rules.moves().collect();
+        Cipher::with_moves(&moves)
+    }
+
+    /// The second part's reading: `X` to lose, `Y` to draw and `Z` to win.
+    pub fn outcomes() -> Self {
+        Cipher {
+            letters: vec![
+                ("X".to_string(), Meaning::Outcome(Outcome::Loss)),
+                ("Y".to_string(), Meaning::Outcome(Outcome::Draw)),
+                ("Z".to_string(), Meaning::Outcome(Outcome::Win)),
+            ],
+        }
+    }
+
+    /// Every way of reading the letters of [`Cipher::moves_in_order`] as
+    /// moves, starting with that one.
+    pub fn move_permutations(rules: &Rules) -> Result<Vec<Self>, String> {
+        if rules.len() > MAX_PERMUTED_MOVES {
+            return Err(format!(
+                "{} moves are too many to try every order of, at most {MAX_PERMUTED_MOVES} are",
+                rules.len()
+            ));
+        }
+        let mut permutations = Vec::new();
+        let mut moves: Vec<Move> = rules.moves().collect();
+        permute(&mut moves, 0, &mut permutations);
+        Ok(permutations
+            .iter()
+            .map(|moves| Cipher::with_moves(moves))
+            .collect())
+    }
+
+    fn with_moves(moves: &[Move]) -> Self {
+        let first_letter = b'Z' + 1 - moves.len() as u8;
+        Cipher {
+            letters: moves
+                .iter()
+                .enumerate()
+                .map(|(index, &a_move)| {
+                    let letter = char::from(first_letter + index as u8).to_string();
+                    (letter, Meaning::Move(a_move))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn decode(&self, token: &str) -> Result<Meaning, ParseError> {
+        self.letters
+            .iter()
+            .find(|(letter, _)| letter == token)
+            .map(|(_, meaning)| *meaning)
+            .ok_or_else(|| {
+                let letters: Vec<&str> = self
+                    .letters
+                    .iter()
+                    .map(|(letter, _)| letter.as_str())
+                    .collect();
+                ParseError::new(token, format!("one of {}", letters.join(", ")))
+            })
+    }
+
+    /// The cipher written out with the names of the moves in `rules`, like
+    /// `X=rock, Y=paper, Z=scissors`.
+    pub fn describe(&self, rules: &Rules) -> String {
+        let letters: Vec<String> = self
+            .letters
+            .iter()
+            .map(|(letter, meaning)| match meaning {
+                Meaning::Move(a_move) => format!("{letter}={}", rules.name(*a_move)),
+                Meaning::Outcome(outcome) => format!("{letter}={outcome}"),
+            })
+            .collect();
+        letters.join(", ")
+    }
+}
+
+/// Heap's algorithm would do with fewer swaps, but this keeps the orders
+/// lexicographic, so the puzzle's own reading comes first.
+fn permute(moves: &mut Vec<Move>, start: usize, permutations: &mut Vec<Vec<Move>>) {
+    if start == moves.len() {
+        permutations.push(moves.clone());
+        return;
+    }
+    for index in start..moves.len() {
+        moves[start..=index].rotate_right(1);
+        permute(moves, start + 1, permutations);
+        moves[start..=index].rotate_left(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_decode_both_readings_of_the_puzzle() {
+        let rules = Rules::rock_paper_scissors();
+        assert_eq!(
+            Cipher::moves_in_order(&rules).decode("Y"),
+            Ok(Meaning::Move(Move(1)))
+        );
+        assert_eq!(
+            Cipher::outcomes().decode("Z"),
+            Ok(Meaning::Outcome(Outcome::Win))
+        );
+        let error = Cipher::outcomes().decode("A").unwrap_err();
+        assert_eq!(error.expected, "one of X, Y, Z");
+    }
+
+    #[test]
+    fn should_enumerate_every_order_of_moves() {
+        let rules = Rules::rock_paper_scissors();
+        let ciphers = Cipher::move_permutations(&rules).unwrap();
+        let described: Vec<String> = ciphers
+            .iter()
+            .map(|cipher| cipher.describe(&rules))
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                "X=rock, Y=paper, Z=scissors",
+                "X=rock, Y=scissors, Z=paper",
+                "X=paper, Y=rock, Z=scissors",
+                "X=paper, Y=scissors, Z=rock",
+                "X=scissors, Y=rock, Z=paper",
+                "X=scissors, Y=paper, Z=rock",
+            ]
+        );
+        assert_eq!(
+            Cipher::move_permutations(&Rules::rock_paper_scissors_lizard_spock())
+                .unwrap()
+                .len(),
+            120
+        );
+    }
+
+    #[test]
+    fn should_reject_letter_standing_for_two_things() {
+        let letters = vec![
+            ("X".to_string(), Meaning::Move(Move(0))),
+            ("X".to_string(), Meaning::Outcome(Outcome::Win)),
+        ];
+        assert!(Cipher::new(letters).is_err());
+    }
+}
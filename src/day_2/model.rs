@@ -0,0 +1,181 @@
+//! The puzzle's own game, plain rock paper scissors. Both parts play it
+//! through [`rules::rounds`], and only differ in the [`Cipher`] their second
+//! column goes through.
+
+use std::{fmt, sync::OnceLock};
+
+use crate::{Error, Input};
+
+use super::{
+    cipher::Cipher,
+    rules::{self, Move, Outcome, Rules},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Choice {
+    pub const ALL: [Choice; 3] = [Choice::Rock, Choice::Paper, Choice::Scissors];
+
+    /// The move in [`Rules::rock_paper_scissors`].
+    pub fn to_move(self) -> Move {
+        match self {
+            Self::Rock => Move(0),
+            Self::Paper => Move(1),
+            Self::Scissors => Move(2),
+        }
+    }
+
+    /// The choice for a move of [`Rules::rock_paper_scissors`], `None` for
+    /// moves of bigger games.
+    pub fn from_move(a_move: Move) -> Option<Self> {
+        Choice::ALL.get(a_move.0).copied()
+    }
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Choice::Rock => "rock",
+            Choice::Paper => "paper",
+            Choice::Scissors => "scissors",
+        })
+    }
+}
+
+/// One round of the guide, played out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub line: usize,
+    pub theirs: Choice,
+    pub mine: Choice,
+    pub outcome: Outcome,
+    /// What this round alone scored.
+    pub score: u64,
+}
+
+/// The rules of the puzzle's game, built once for every guide played.
+fn puzzle_rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(Rules::rock_paper_scissors)
+}
+
+/// Plays the guide in `input` round by round, with its second column read
+/// through `cipher`.
+pub fn rounds(input: Input, cipher: &Cipher) -> impl Iterator<Item = Result<Round, Error>> + '_ {
+    rules::rounds(input, puzzle_rules(), cipher).map(|round| {
+        let round = round?;
+        // the rounds only hold moves of the rules they are played by
+        let choice = |a_move| Choice::from_move(a_move).expect("a move of rock paper scissors");
+        Ok(Round {
+            line: round.line,
+            theirs: choice(round.theirs),
+            mine: choice(round.mine),
+            outcome: round.outcome,
+            score: round.score,
+        })
+    })
+}
+
+/// Plays the guide in `input` with its second column read through `cipher`,
+/// adding up my score over every round.
+pub fn score_guide(input: Input, cipher: &Cipher) -> Result<u64, Error> {
+    rounds(input, cipher).try_fold(0, |score: u64, round| {
+        let round = round?;
+        rules::add_score(score, round.score, round.line)
+    })
+}
+
+/// How a round where I play `mine` against `theirs` goes for me.
+pub fn outcome(mine: Choice, theirs: Choice) -> Outcome {
+    puzzle_rules().outcome(mine.to_move(), theirs.to_move())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every line the guide can hold, with the score it is worth in the first
+    /// and the second part, worked out by hand.
+    const ROUNDS: [(&str, u64, u64); 9] = [
+        ("A X", 4, 3),
+        ("A Y", 8, 4),
+        ("A Z", 3, 8),
+        ("B X", 1, 1),
+        ("B Y", 5, 5),
+        ("B Z", 9, 9),
+        ("C X", 7, 2),
+        ("C Y", 2, 6),
+        ("C Z", 6, 7),
+    ];
+
+    #[test]
+    fn should_score_every_round_reading_moves() {
+        let cipher = Cipher::moves_in_order(&Rules::rock_paper_scissors());
+        for (line, score, _) in ROUNDS {
+            assert_eq!(
+                score_guide(Input::from_text(line), &cipher).unwrap(),
+                score,
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_score_every_round_reading_outcomes() {
+        let cipher = Cipher::outcomes();
+        for (line, _, score) in ROUNDS {
+            assert_eq!(
+                score_guide(Input::from_text(line), &cipher).unwrap(),
+                score,
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_agree_with_rules_on_every_outcome() {
+        for mine in Choice::ALL {
+            for theirs in Choice::ALL {
+                let expected = match (mine, theirs) {
+                    (Choice::Rock, Choice::Scissors)
+                    | (Choice::Paper, Choice::Rock)
+                    | (Choice::Scissors, Choice::Paper) => Outcome::Win,
+                    _ if mine == theirs => Outcome::Draw,
+                    _ => Outcome::Loss,
+                };
+                assert_eq!(
+                    outcome(mine, theirs),
+                    expected,
+                    "{mine:?} against {theirs:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_report_unknown_opponent_choice() {
+        let cipher = Cipher::outcomes();
+        match score_guide(Input::from_text("A X\nB Y\nD X\n"), &cipher) {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (3, 1));
+                assert_eq!(error.expected, "one of A, B, C");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_reject_cipher_of_a_bigger_game() {
+        let cipher = Cipher::moves_in_order(&Rules::rock_paper_scissors_lizard_spock());
+        let error = score_guide(Input::from_text("A X\nA Z\n"), &cipher).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: Z stands for move 5, but the rules only have 3 moves"
+        );
+    }
+}
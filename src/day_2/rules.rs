@@ -0,0 +1,524 @@
+//! Rules of rock-paper-scissors like games as data: which moves there are,
+//! which move beats which, and what every move and outcome scores.
+//!
+//! Besides the built-in games, rules can be written in TOML:
+//!
+//! ```toml
+//! [outcomes]        # optional, these are the defaults
+//! win = 6
+//! draw = 3
+//! loss = 0
+//!
+//! [[moves]]
+//! name = "rock"
+//! score = 1         # optional, defaults to the position of the move
+//! beats = ["scissors"]
+//!
+//! [[moves]]
+//! name = "paper"
+//! beats = ["rock"]
+//!
+//! [[moves]]
+//! name = "scissors"
+//! beats = ["paper"]
+//! ```
+//!
+//! When no move lists what it beats, the game is cyclic: every move beats the
+//! half of the moves listed right before it, wrapping around, which takes an
+//! odd number of moves.
+
+use std::{fmt, fs, iter::Enumerate, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    input::{Lines, Origin},
+    trace, Error, Input, ParseError,
+};
+
+use super::cipher::{Cipher, Meaning};
+
+/// A move of a game, as its position in the moves of the [`Rules`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Move(pub usize);
+
+/// How a round went, from my side of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Win => "win",
+            Outcome::Draw => "draw",
+            Outcome::Loss => "loss",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    /// `beats[a][b]` when move `a` beats move `b`.
+    beats: Vec<Vec<bool>>,
+    win: u32,
+    draw: u32,
+    loss: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    moves: Vec<MoveConfig>,
+    #[serde(default)]
+    outcomes: OutcomeScores,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveConfig {
+    name: String,
+    score: Option<u32>,
+    beats: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+struct OutcomeScores {
+    win: u32,
+    draw: u32,
+    loss: u32,
+}
+
+impl Default for OutcomeScores {
+    fn default() -> Self {
+        OutcomeScores {
+            win: 6,
+            draw: 3,
+            loss: 0,
+        }
+    }
+}
+
+/// Moves are written down as letters in the strategy guide, so there can be no
+/// more of them than letters.
+const MAX_MOVES: usize = 26;
+
+impl Rules {
+    /// The game of the puzzle.
+    pub fn rock_paper_scissors() -> Self {
+        Rules::cyclic(&["rock", "paper", "scissors"]).expect("three moves make a cyclic game")
+    }
+
+    /// Rock-paper-scissors with two more moves. Listed in the order that makes
+    /// the cyclic rule match the usual one, so rock scores 1, spock 2, paper 3,
+    /// lizard 4 and scissors 5.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Rules::cyclic(&["rock", "spock", "paper", "lizard", "scissors"])
+            .expect("five moves make a cyclic game")
+    }
+
+    /// Game where every move beats the half of the moves listed right before
+    /// it, scoring moves by their 1-based position.
+    pub fn cyclic(names: &[&str]) -> Result<Self, String> {
+        let moves = names
+            .iter()
+            .map(|name| MoveConfig {
+                name: name.to_string(),
+                score: None,
+                beats: None,
+            })
+            .collect();
+        Rules::from_config(Config {
+            moves,
+            outcomes: OutcomeScores::default(),
+        })
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let config: Config = toml::from_str(text).map_err(|error| error.to_string())?;
+        Rules::from_config(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let origin = Origin::Path(path.to_path_buf());
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            origin: origin.clone(),
+            source,
+        })?;
+        Rules::from_toml(&text).map_err(|message| Error::InvalidFile { origin, message })
+    }
+
+    /// Rules by the name of a built-in game, `rps` or `rpsls`, or else loaded
+    /// from the TOML file at `name`.
+    pub fn named(name: &str) -> Result<Self, Error> {
+        match name {
+            "rps" => Ok(Rules::rock_paper_scissors()),
+            "rpsls" => Ok(Rules::rock_paper_scissors_lizard_spock()),
+            path => Rules::load(Path::new(path)),
+        }
+    }
+
+    fn from_config(config: Config) -> Result<Self, String> {
+        let count = config.moves.len();
+        if !(1..=MAX_MOVES).contains(&count) {
+            return Err(format!("expected 1 to {MAX_MOVES} moves, found {count}"));
+        }
+        let names: Vec<String> = config.moves.iter().map(|rule| rule.name.clone()).collect();
+        for (index, name) in names.iter().enumerate() {
+            if names[..index].contains(name) {
+                return Err(format!("move {name} is listed twice"));
+            }
+        }
+        let scores = config
+            .moves
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| rule.score.unwrap_or(index as u32 + 1))
+            .collect();
+
+        let mut beats = vec![vec![false; count]; count];
+        if config.moves.iter().all(|rule| rule.beats.is_none()) {
+            if count.is_multiple_of(2) {
+                return Err(format!(
+                    "a cyclic game needs an odd number of moves, found {count}"
+                ));
+            }
+            for (winner, row) in beats.iter_mut().enumerate() {
+                for distance in 1..=count / 2 {
+                    row[(winner + count - distance) % count] = true;
+                }
+            }
+        } else {
+            for (winner, rule) in config.moves.iter().enumerate() {
+                for loser_name in rule.beats.iter().flatten() {
+                    let loser = names
+                        .iter()
+                        .position(|name| name == loser_name)
+                        .ok_or_else(|| {
+                            format!("move {} beats {loser_name}, which is no move", rule.name)
+                        })?;
+                    if loser == winner {
+                        return Err(format!("move {} cannot beat itself", rule.name));
+                    }
+                    beats[winner][loser] = true;
+                }
+            }
+            for (winner, row) in beats.iter().enumerate() {
+                for (loser, &wins) in row.iter().enumerate() {
+                    if wins && beats[loser][winner] {
+                        return Err(format!(
+                            "moves {} and {} cannot both beat each other",
+                            names[winner], names[loser]
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(Rules {
+            names,
+            scores,
+            beats,
+            win: config.outcomes.win,
+            draw: config.outcomes.draw,
+            loss: config.outcomes.loss,
+        })
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.names.len()).map(Move)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, a_move: Move) -> &str {
+        &self.names[a_move.0]
+    }
+
+    pub fn move_named(&self, name: &str) -> Option<Move> {
+        self.names.iter().position(|known| known == name).map(Move)
+    }
+
+    pub fn move_score(&self, a_move: Move) -> u32 {
+        self.scores[a_move.0]
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        match outcome {
+            Outcome::Win => self.win,
+            Outcome::Draw => self.draw,
+            Outcome::Loss => self.loss,
+        }
+    }
+
+    /// How playing `mine` against `theirs` goes for me. Moves neither beating
+    /// the other draw.
+    pub fn outcome(&self, mine: Move, theirs: Move) -> Outcome {
+        if self.beats[mine.0][theirs.0] {
+            Outcome::Win
+        } else if self.beats[theirs.0][mine.0] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// First move, in the order of the rules, that gets me `outcome` against
+    /// `theirs`, if there is any.
+    pub fn move_for(&self, theirs: Move, outcome: Outcome) -> Option<Move> {
+        self.moves()
+            .find(|&mine| self.outcome(mine, theirs) == outcome)
+    }
+
+    /// My score for a round of `mine` against `theirs`, wider than the scores
+    /// it adds up so any rules file gets it right.
+    pub fn round_score(&self, mine: Move, theirs: Move) -> u64 {
+        u64::from(self.move_score(mine)) + u64::from(self.outcome_score(self.outcome(mine, theirs)))
+    }
+}
+
+/// One round of the strategy guide, played out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub line: usize,
+    pub theirs: Move,
+    pub mine: Move,
+    pub outcome: Outcome,
+    /// What this round alone scored.
+    pub score: u64,
+}
+
+/// Plays the strategy guide in `input` round by round under any `rules`,
+/// reading the second column through `cipher`.
+///
+/// The opponent's moves are written `A`, `B`, `C` and so on, in the order of
+/// the rules.
+pub fn rounds<'a>(input: Input, rules: &'a Rules, cipher: &'a Cipher) -> Rounds<'a> {
+    let theirs = rules
+        .moves()
+        .map(|Move(index)| char::from(b'A' + index as u8).to_string())
+        .collect();
+    Rounds {
+        lines: input.lines().enumerate(),
+        rules,
+        cipher,
+        theirs,
+    }
+}
+
+pub struct Rounds<'a> {
+    lines: Enumerate<Lines>,
+    rules: &'a Rules,
+    cipher: &'a Cipher,
+    /// Letters of the opponent's moves.
+    theirs: Vec<String>,
+}
+
+impl Rounds<'_> {
+    fn round(&self, line_data: &str, line_number: usize) -> Result<Round, Error> {
+        let rules = self.rules;
+        let (first, second) = line_data.split_once(' ').ok_or_else(|| {
+            ParseError::new(line_data, "two columns separated by a space").on_line(line_number)
+        })?;
+        let their_move = self
+            .theirs
+            .iter()
+            .position(|letter| letter == first)
+            .map(Move)
+            .ok_or_else(|| {
+                ParseError::new(first, format!("one of {}", self.theirs.join(", ")))
+                    .on_line(line_number)
+            })?;
+        let meaning = self
+            .cipher
+            .decode(second)
+            .map_err(|error| error.shifted(first.len() + 1).on_line(line_number))?;
+        let my_move = match meaning {
+            Meaning::Move(mine) if mine.0 >= rules.len() => {
+                return Err(Error::Unsolvable {
+                    message: format!(
+                        "line {line_number}: {second} stands for move {}, but the rules only \
+                         have {} moves",
+                        mine.0 + 1,
+                        rules.len()
+                    ),
+                })
+            }
+            Meaning::Move(mine) => mine,
+            Meaning::Outcome(outcome) => {
+                rules
+                    .move_for(their_move, outcome)
+                    .ok_or_else(|| Error::InvalidOption {
+                        name: "rules".to_string(),
+                        message: format!(
+                            "no move gets a {outcome} against {}, needed on line {line_number}",
+                            rules.name(their_move)
+                        ),
+                    })?
+            }
+        };
+        let outcome = rules.outcome(my_move, their_move);
+        trace!(
+            "{} against {}: {}",
+            rules.name(my_move),
+            rules.name(their_move),
+            outcome
+        );
+        Ok(Round {
+            line: line_number,
+            theirs: their_move,
+            mine: my_move,
+            outcome,
+            score: rules.round_score(my_move, their_move),
+        })
+    }
+}
+
+impl Iterator for Rounds<'_> {
+    type Item = Result<Round, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line_index, line) = self.lines.next()?;
+        Some(line.and_then(|line_data| self.round(&line_data, line_index + 1)))
+    }
+}
+
+/// Scores the strategy guide in `input` under any `rules`, reading the second
+/// column through `cipher`, as laid out in [`rounds`].
+pub fn play(input: Input, rules: &Rules, cipher: &Cipher) -> Result<u64, Error> {
+    rounds(input, rules, cipher).try_fold(0, |score: u64, round| {
+        let round = round?;
+        add_score(score, round.score, round.line)
+    })
+}
+
+/// Adds the score of the round on `line` to the `total` so far, failing
+/// rather than wrapping around.
+pub fn add_score(total: u64, score: u64, line: usize) -> Result<u64, Error> {
+    total.checked_add(score).ok_or_else(|| Error::Overflow {
+        what: format!("score at line {line}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_follow_rock_paper_scissors() {
+        let rules = Rules::rock_paper_scissors();
+        let [rock, paper, scissors] = [0, 1, 2].map(Move);
+        assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+        assert_eq!(rules.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(rules.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rules.outcome(rock, scissors), Outcome::Win);
+        assert_eq!(rules.move_for(scissors, Outcome::Win), Some(rock));
+        assert_eq!(rules.round_score(scissors, paper), 3 + 6);
+    }
+
+    #[test]
+    fn should_follow_rock_paper_scissors_lizard_spock() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        let name = |name| rules.move_named(name).unwrap();
+        let wins = [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(rules.outcome(name(winner), name(loser)), Outcome::Win);
+            assert_eq!(rules.outcome(name(loser), name(winner)), Outcome::Loss);
+        }
+    }
+
+    #[test]
+    fn should_load_rules_from_toml() {
+        let rules = Rules::from_toml(
+            "[outcomes]\nwin = 10\n\n\
+             [[moves]]\nname = \"fire\"\nscore = 5\nbeats = [\"grass\"]\n\n\
+             [[moves]]\nname = \"water\"\nbeats = [\"fire\"]\n\n\
+             [[moves]]\nname = \"grass\"\nbeats = [\"water\"]\n",
+        )
+        .unwrap();
+        let [fire, water, grass] = [0, 1, 2].map(Move);
+        assert_eq!(rules.round_score(fire, grass), 5 + 10);
+        assert_eq!(rules.round_score(water, grass), 2);
+        assert_eq!(rules.round_score(grass, grass), 3 + 3);
+    }
+
+    #[test]
+    fn should_score_the_largest_scores_without_overflowing() {
+        let rules = Rules::from_toml(
+            "[outcomes]\nwin = 4294967295\n\n\
+             [[moves]]\nname = \"big\"\nscore = 4294967295\nbeats = [\"small\"]\n\n\
+             [[moves]]\nname = \"small\"\nbeats = []\n",
+        )
+        .unwrap();
+        let [big, small] = [0, 1].map(Move);
+        assert_eq!(rules.round_score(big, small), 2 * u64::from(u32::MAX));
+
+        assert_eq!(add_score(1, 2, 1).unwrap(), 3);
+        let error = add_score(u64::MAX, 1, 7).unwrap_err();
+        assert_eq!(error.to_string(), "score at line 7 overflowed");
+    }
+
+    #[test]
+    fn should_reject_inconsistent_rules() {
+        assert!(Rules::cyclic(&["rock", "paper"]).is_err());
+        assert!(Rules::cyclic(&[]).is_err());
+        assert!(Rules::cyclic(&["rock", "rock", "paper"]).is_err());
+        let both_ways = "[[moves]]\nname = \"a\"\nbeats = [\"b\"]\n\n\
+                         [[moves]]\nname = \"b\"\nbeats = [\"a\"]\n";
+        assert!(Rules::from_toml(both_ways).is_err());
+        let unknown = "[[moves]]\nname = \"a\"\nbeats = [\"c\"]\n";
+        assert!(Rules::from_toml(unknown).is_err());
+    }
+
+    #[test]
+    fn should_play_guide_under_any_rules() {
+        let rules = Rules::rock_paper_scissors_lizard_spock();
+        // rock against spock, then lizard against paper
+        let input = Input::from_text("A W\nC Y\n");
+        let cipher = Cipher::moves_in_order(&rules);
+        assert_eq!(play(input, &rules, &cipher).unwrap(), 2 + 6 + 4 + 6);
+
+        // rock to lose, the first move losing to rock is lizard
+        let input = Input::from_text("A X\n");
+        assert_eq!(play(input, &rules, &Cipher::outcomes()).unwrap(), 4);
+
+        let input = Input::from_text("A Q\n");
+        match play(input, &rules, &cipher) {
+            Err(Error::Parse(error)) => assert_eq!(error.expected, "one of V, W, X, Y, Z"),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_name_outcome_no_move_gets() {
+        let rules = Rules::cyclic(&["a"]).unwrap();
+        let error = play(Input::from_text("A Z\n"), &rules, &Cipher::outcomes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "option rules: no move gets a win against a, needed on line 1"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Plays the strategy guide out round by round, writing down what happened in
+//! each round instead of only the final score.
+
+use std::{fmt::Write, str::FromStr};
+
+use crate::Error;
+
+use super::{
+    model::Round,
+    rules::{add_score, Outcome},
+};
+
+/// How the transcript is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Aligned columns, followed by the counts of every outcome.
+    Text,
+    /// One row per round under a header, then, after a blank line, the counts
+    /// of every outcome as a table of their own, so that both load into a
+    /// spreadsheet.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            _ => Err("format should be text or csv".to_string()),
+        }
+    }
+}
+
+/// How many rounds ended which way, and the score they add up to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: u64,
+}
+
+impl Tally {
+    pub fn add(&mut self, round: &Round) -> Result<(), Error> {
+        match round.outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+        self.score = add_score(self.score, round.score, round.line)?;
+        Ok(())
+    }
+}
+
+/// Writes a transcript of `rounds`, each with the score so far.
+pub fn transcript(
+    rounds: impl Iterator<Item = Result<Round, Error>>,
+    format: Format,
+) -> Result<String, Error> {
+    // as wide as the longest of the header and `scissors`
+    let width = "opponent".len();
+
+    let mut transcript = String::new();
+    match format {
+        Format::Text => writeln!(
+            transcript,
+            "round  {:width$}  {:width$}  outcome  score",
+            "opponent", "me"
+        ),
+        Format::Csv => writeln!(transcript, "round,opponent,me,outcome,score"),
+    }
+    .expect("writing to a string does not fail");
+
+    let mut tally = Tally::default();
+    for (index, round) in rounds.enumerate() {
+        let round = round?;
+        tally.add(&round)?;
+        match format {
+            Format::Text => writeln!(
+                transcript,
+                "{:>5}  {:width$}  {:width$}  {:7}  {:>5}",
+                index + 1,
+                round.theirs,
+                round.mine,
+                round.outcome,
+                tally.score
+            ),
+            Format::Csv => writeln!(
+                transcript,
+                "{},{},{},{},{}",
+                index + 1,
+                round.theirs,
+                round.mine,
+                round.outcome,
+                tally.score
+            ),
+        }
+        .expect("writing to a string does not fail");
+    }
+
+    match format {
+        Format::Text => write!(
+            transcript,
+            "wins: {}, draws: {}, losses: {}, score: {}",
+            tally.wins, tally.draws, tally.losses, tally.score
+        ),
+        Format::Csv => write!(
+            transcript,
+            "\nwins,draws,losses,score\n{},{},{},{}",
+            tally.wins, tally.draws, tally.losses, tally.score
+        ),
+    }
+    .expect("writing to a string does not fail");
+    Ok(transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_2::{
+            cipher::Cipher,
+            model::{self, Choice},
+            rules::Rules,
+        },
+        Input,
+    };
+
+    use super::*;
+
+    fn example() -> Input {
+        Input::from_path("./inputs/day_2/mocks/example.txt").unwrap()
+    }
+
+    #[test]
+    fn should_write_transcript_as_text() {
+        let cipher = Cipher::moves_in_order(&Rules::rock_paper_scissors());
+        let rounds = model::rounds(example(), &cipher);
+        assert_eq!(
+            transcript(rounds, Format::Text).unwrap(),
+            "round  opponent  me        outcome  score\n\
+             \x20   1  rock      paper     win          8\n\
+             \x20   2  paper     rock      loss         9\n\
+             \x20   3  scissors  scissors  draw        15\n\
+             wins: 1, draws: 1, losses: 1, score: 15"
+        );
+    }
+
+    #[test]
+    fn should_write_transcript_as_csv() {
+        let cipher = Cipher::outcomes();
+        let rounds = model::rounds(example(), &cipher);
+        assert_eq!(
+            transcript(rounds, Format::Csv).unwrap(),
+            "round,opponent,me,outcome,score\n\
+             1,rock,rock,draw,4\n\
+             2,paper,rock,loss,5\n\
+             3,scissors,rock,win,12\n\
+             \n\
+             wins,draws,losses,score\n\
+             1,1,1,12"
+        );
+    }
+
+    #[test]
+    fn should_play_rounds_as_choices() {
+        let round = model::rounds(Input::from_text("C X\n"), &Cipher::outcomes())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (round.theirs, round.mine),
+            (Choice::Scissors, Choice::Paper)
+        );
+        assert_eq!((round.outcome, round.score), (Outcome::Loss, 2));
+    }
+}
@@ -1,2 +1,58 @@
+pub mod common_item;
+pub mod diagnostics;
 mod first_part;
+pub mod priority_set;
+pub mod search_tree;
 mod second_part;
+
+use crate::{mode, Error, Input, Mode, Options, Solver};
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::sum_of_item_priorities(input)
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::sum_of_badge_priorities(input)
+    }
+
+    fn modes(&self) -> &'static [Mode] {
+        &[
+            Mode {
+                name: "badges",
+                about: "sum of badge priorities with elves in groups of another size (size=3)",
+            },
+            Mode {
+                name: "report",
+                about: "compartments, shared items and problems of every rucksack",
+            },
+        ]
+    }
+
+    fn run_mode(&self, mode: &str, options: &Options, input: Input) -> Result<String, Error> {
+        match mode {
+            "badges" => {
+                let size = options.parse_or("size", second_part::GROUP_SIZE)?;
+                second_part::sum_of_badge_priorities_in_groups_of(input, size)
+            }
+            "report" => Ok(diagnostics::Diagnostics::from_input(input)?.report()),
+            _ => Err(mode::unknown(mode, self.modes())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_badges_of_groups_of_two() {
+        let input = Input::from_text("abX\ncdX\nefg\nghi\n");
+        let options: Options = [("size".to_string(), "2".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(Day3.run_mode("badges", &options, input).unwrap(), "57");
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::{btree_map, BTreeMap};
+
+/// Counts how many times every item was added, a multiset.
+///
+/// Items are kept in order, so iterating, and picking between items counted
+/// equally often, goes the same way every run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommonItem<T> {
+    inner: BTreeMap<T, usize>,
+}
+
+impl<T> Default for CommonItem<T> {
+    fn default() -> Self {
+        CommonItem {
+            inner: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord> CommonItem<T> {
+    pub fn new() -> Self {
+        CommonItem::default()
+    }
+
+    /// Counts `item` once more, returning how many times it is counted now.
+    pub fn add(&mut self, item: T) -> usize {
+        let count = self.inner.entry(item).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// How many times `item` was added, `0` when it never was.
+    pub fn count(&self, item: &T) -> usize {
+        self.inner.get(item).copied().unwrap_or(0)
+    }
+
+    /// Number of different items.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Number of items added, counting every time an item was added.
+    pub fn total(&self) -> usize {
+        self.inner.values().sum()
+    }
+
+    /// The `n` items counted most often with their counts, most often first,
+    /// and the smallest first among those counted equally often.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut counts: Vec<(&T, usize)> = self.iter().collect();
+        // stable, so equal counts stay smallest item first
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+        counts.truncate(n);
+        counts
+    }
+
+    /// Items with exactly `count` of them, smallest first.
+    pub fn counted(&self, count: usize) -> impl Iterator<Item = &T> {
+        self.iter()
+            .filter(move |(_, times)| *times == count)
+            .map(|(item, _)| item)
+    }
+
+    /// Items with their counts, smallest item first.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.inner.iter().map(|(item, count)| (item, *count))
+    }
+}
+
+impl<T: Ord + Clone> CommonItem<T> {
+    /// Items both multisets hold, each counted as often as the multiset
+    /// holding fewer of them does.
+    pub fn intersection(&self, other: &CommonItem<T>) -> CommonItem<T> {
+        let inner = self
+            .iter()
+            .filter_map(|(item, count)| {
+                let common = count.min(other.count(item));
+                (common > 0).then(|| (item.clone(), common))
+            })
+            .collect();
+        CommonItem { inner }
+    }
+}
+
+impl<T: Ord> Extend<T> for CommonItem<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for CommonItem<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut common_item = CommonItem::new();
+        common_item.extend(iter);
+        common_item
+    }
+}
+
+impl<T> IntoIterator for CommonItem<T> {
+    type Item = (T, usize);
+    type IntoIter = btree_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_count_every_addition_once() {
+        let mut common_item = CommonItem::new();
+        assert_eq!(common_item.add('a'), 1);
+        assert_eq!(common_item.add('a'), 2);
+        assert_eq!(common_item.add('b'), 1);
+        assert_eq!(common_item.count(&'a'), 2);
+        assert_eq!(common_item.count(&'b'), 1);
+        assert_eq!(common_item.count(&'c'), 0);
+        assert_eq!((common_item.len(), common_item.total()), (2, 3));
+    }
+
+    #[test]
+    fn should_rank_most_common_items() {
+        let common_item: CommonItem<char> = "abracadabra".chars().collect();
+        assert_eq!(
+            common_item.most_common(3),
+            vec![(&'a', 5), (&'b', 2), (&'r', 2)]
+        );
+        assert_eq!(common_item.most_common(10).len(), 5);
+        assert_eq!(common_item.counted(1).collect::<Vec<_>>(), vec![&'c', &'d']);
+    }
+
+    #[test]
+    fn should_intersect_with_lowest_counts() {
+        let first: CommonItem<char> = "aaabbc".chars().collect();
+        let second: CommonItem<char> = "abbbbd".chars().collect();
+        let common = first.intersection(&second);
+        assert_eq!(
+            common.into_iter().collect::<Vec<_>>(),
+            vec![('a', 1), ('b', 2)]
+        );
+        assert!(first.intersection(&CommonItem::new()).is_empty());
+    }
+}
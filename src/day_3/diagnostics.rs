@@ -0,0 +1,225 @@
+//! A look at every rucksack of the input, for when a priority sum comes out
+//! wrong: what each compartment holds, what they share, and what about the
+//! rucksack does not fit the puzzle, all without stopping at the first
+//! problem.
+
+use std::fmt;
+
+use crate::{Error, Input, ParseError};
+
+use super::{
+    common_item::CommonItem,
+    priority_set::{item_of, PrioritySet},
+};
+
+/// Something about a rucksack the puzzle does not allow for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The items do not split into two compartments of the same size.
+    OddLength { items: usize },
+    /// A character that is not an item, pointing at where it is.
+    NotAnItem(ParseError),
+    /// The compartments should share exactly one item.
+    SharedItems(PrioritySet),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::OddLength { items } => {
+                write!(f, "{items} items do not split into two compartments")
+            }
+            Problem::NotAnItem(error) => write!(f, "{error}"),
+            Problem::SharedItems(shared) if shared.is_empty() => {
+                write!(f, "compartments share no item")
+            }
+            Problem::SharedItems(shared) => {
+                write!(f, "compartments share {} items", shared.len())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack {
+    pub line: usize,
+    pub first_compartment: String,
+    pub second_compartment: String,
+    /// Items in both compartments, `None` when a compartment could not be
+    /// read.
+    pub shared: Option<PrioritySet>,
+    pub problems: Vec<Problem>,
+}
+
+impl Rucksack {
+    /// Looks `items` over, the rucksack on line `line`.
+    pub fn inspect(items: &str, line: usize) -> Self {
+        let mut problems = Vec::new();
+        let item_count = items.chars().count();
+        if !item_count.is_multiple_of(2) {
+            problems.push(Problem::OddLength { items: item_count });
+        }
+
+        let middle = items
+            .char_indices()
+            .nth(item_count / 2)
+            .map_or(items.len(), |(offset, _)| offset);
+        let (first, second) = items.split_at(middle);
+        let first_set = first.parse::<PrioritySet>();
+        let second_set = second
+            .parse::<PrioritySet>()
+            .map_err(|error| error.shifted(item_count / 2));
+        let shared = match (first_set, second_set) {
+            (Ok(first), Ok(second)) => Some(first.intersection(second)),
+            (first, second) => {
+                for error in [first.err(), second.err()].into_iter().flatten() {
+                    problems.push(Problem::NotAnItem(error.on_line(line)));
+                }
+                None
+            }
+        };
+        if let Some(shared) = shared.filter(|shared| shared.len() != 1) {
+            problems.push(Problem::SharedItems(shared));
+        }
+
+        Rucksack {
+            line,
+            first_compartment: first.to_string(),
+            second_compartment: second.to_string(),
+            shared,
+            problems,
+        }
+    }
+}
+
+/// Every rucksack of an input, looked over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub rucksacks: Vec<Rucksack>,
+    /// How often each priority was shared between the compartments of a
+    /// rucksack.
+    pub shared_priorities: CommonItem<u8>,
+}
+
+impl Diagnostics {
+    pub fn from_input(input: Input) -> Result<Self, Error> {
+        let mut rucksacks = Vec::new();
+        let mut shared_priorities = CommonItem::new();
+        for (line_index, line) in input.lines().enumerate() {
+            let rucksack = Rucksack::inspect(&line?, line_index + 1);
+            shared_priorities.extend(rucksack.shared.into_iter().flatten());
+            rucksacks.push(rucksack);
+        }
+        Ok(Diagnostics {
+            rucksacks,
+            shared_priorities,
+        })
+    }
+
+    /// Rucksacks with at least one problem.
+    pub fn with_problems(&self) -> impl Iterator<Item = &Rucksack> {
+        self.rucksacks
+            .iter()
+            .filter(|rucksack| !rucksack.problems.is_empty())
+    }
+
+    /// One line per rucksack, its problems below it, and a summary of the
+    /// items shared across the input.
+    pub fn report(&self) -> String {
+        let mut lines = Vec::new();
+        for rucksack in &self.rucksacks {
+            let shared = match rucksack.shared {
+                Some(shared) if !shared.is_empty() => describe(shared.iter()),
+                Some(_) => "nothing".to_string(),
+                None => "?".to_string(),
+            };
+            lines.push(format!(
+                "{:>5}  {} | {}  shared: {shared}",
+                rucksack.line, rucksack.first_compartment, rucksack.second_compartment
+            ));
+            for problem in &rucksack.problems {
+                lines.push(format!("       ! {problem}"));
+            }
+        }
+
+        lines.push(format!(
+            "rucksacks: {}, with problems: {}",
+            self.rucksacks.len(),
+            self.with_problems().count()
+        ));
+        let most_common: Vec<String> = self
+            .shared_priorities
+            .most_common(usize::MAX)
+            .into_iter()
+            .map(|(&priority, count)| format!("{} x{count}", describe([priority])))
+            .collect();
+        lines.push(format!("shared items: {}", most_common.join(", ")));
+        let sum: u64 = self
+            .shared_priorities
+            .iter()
+            .map(|(&priority, count)| u64::from(priority) * count as u64)
+            .sum();
+        lines.push(format!("sum of shared priorities: {sum}"));
+        lines.join("\n")
+    }
+}
+
+/// Items with their priorities, like `p (16), L (38)`.
+fn describe(priorities: impl IntoIterator<Item = u8>) -> String {
+    let items: Vec<String> = priorities
+        .into_iter()
+        .map(|priority| match item_of(priority) {
+            Some(item) => format!("{item} ({priority})"),
+            None => priority.to_string(),
+        })
+        .collect();
+    items.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_example_without_problems() {
+        let input = Input::from_path("./inputs/day_3/mocks/example.txt").unwrap();
+        let diagnostics = Diagnostics::from_input(input).unwrap();
+        assert_eq!(diagnostics.with_problems().count(), 0);
+        let report = diagnostics.report();
+        assert!(report.starts_with("    1  vJrwpWtwJgWr | hcsFMMfFFhFp  shared: p (16)\n"));
+        assert!(report.ends_with(
+            "rucksacks: 6, with problems: 0\n\
+             shared items: p (16) x1, s (19) x1, t (20) x1, v (22) x1, L (38) x1, P (42) x1\n\
+             sum of shared priorities: 157"
+        ));
+    }
+
+    #[test]
+    fn should_report_every_problem_with_its_line() {
+        let input = Input::from_text("abcab\nab1ab\nabcd\nabAB\n");
+        let diagnostics = Diagnostics::from_input(input).unwrap();
+        let problems: Vec<(usize, String)> = diagnostics
+            .with_problems()
+            .flat_map(|rucksack| {
+                rucksack
+                    .problems
+                    .iter()
+                    .map(|problem| (rucksack.line, problem.to_string()))
+            })
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (1, "5 items do not split into two compartments".to_string()),
+                (1, "compartments share 2 items".to_string()),
+                (2, "5 items do not split into two compartments".to_string()),
+                (
+                    2,
+                    "line 2, column 3: expected an item, a to z or A to Z, found \"1\"".to_string()
+                ),
+                (3, "compartments share no item".to_string()),
+                (4, "compartments share no item".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::{fmt, str::FromStr};
+
+use crate::ParseError;
+
+/// Highest priority an item can have, `Z`'s.
+pub const MAX_PRIORITY: u8 = 52;
+
+/// Priority of an item: 1 through 26 for `a` through `z`, and 27 through 52
+/// for `A` through `Z`. `None` for anything that is not an item.
+pub fn priority_of(item: char) -> Option<u8> {
+    match item {
+        'a'..='z' => Some(item as u8 - b'a' + 1),
+        'A'..='Z' => Some(item as u8 - b'A' + 27),
+        _ => None,
+    }
+}
+
+/// Item of a priority, the other way around from [`priority_of`].
+pub fn item_of(priority: u8) -> Option<char> {
+    match priority {
+        1..=26 => Some(char::from(b'a' + priority - 1)),
+        27..=MAX_PRIORITY => Some(char::from(b'A' + priority - 27)),
+        _ => None,
+    }
+}
+
+/// Set of item priorities, one bit of a `u64` each.
+///
+/// With only 52 priorities there is no need for anything fancier: adding an
+/// item or looking one up is a single bit operation, and the items two
+/// rucksacks have in common are a single `&` of their sets, without
+/// allocating anything.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PrioritySet {
+    bits: u64,
+}
+
+impl PrioritySet {
+    pub fn new() -> Self {
+        PrioritySet::default()
+    }
+
+    /// Adds `priority`, which has to be one of `1..=52`.
+    pub fn insert(&mut self, priority: u8) {
+        assert!(
+            (1..=MAX_PRIORITY).contains(&priority),
+            "priority {priority} is not one of 1..={MAX_PRIORITY}"
+        );
+        self.bits |= 1 << priority;
+    }
+
+    pub fn contains(&self, priority: u8) -> bool {
+        priority <= MAX_PRIORITY && self.bits & (1 << priority) != 0
+    }
+
+    pub fn union(self, other: PrioritySet) -> PrioritySet {
+        PrioritySet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    pub fn intersection(self, other: PrioritySet) -> PrioritySet {
+        PrioritySet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The priorities in the set, lowest first.
+    pub fn iter(&self) -> Priorities {
+        Priorities { bits: self.bits }
+    }
+}
+
+impl FromIterator<u8> for PrioritySet {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        let mut set = PrioritySet::new();
+        for priority in iter {
+            set.insert(priority);
+        }
+        set
+    }
+}
+
+impl IntoIterator for PrioritySet {
+    type Item = u8;
+    type IntoIter = Priorities;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Reads the items of a rucksack or compartment, like `vJrwpWtwJgWr`.
+impl FromStr for PrioritySet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = PrioritySet::new();
+        for (offset, item) in s.chars().enumerate() {
+            let priority = priority_of(item).ok_or_else(|| {
+                ParseError::new(&item.to_string(), "an item, a to z or A to Z").shifted(offset)
+            })?;
+            set.insert(priority);
+        }
+        Ok(set)
+    }
+}
+
+/// Shown as the items, like `{a, p, L}`, which reads easier than priorities.
+impl fmt::Debug for PrioritySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().filter_map(item_of))
+            .finish()
+    }
+}
+
+/// Iterator over the priorities of a [`PrioritySet`], lowest first.
+#[derive(Debug, Clone)]
+pub struct Priorities {
+    bits: u64,
+}
+
+impl Iterator for Priorities {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            return None;
+        }
+        let priority = self.bits.trailing_zeros() as u8;
+        // clears the lowest set bit
+        self.bits &= self.bits - 1;
+        Some(priority)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Priorities {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_map_every_item_to_its_priority_and_back() {
+        assert_eq!(priority_of('a'), Some(1));
+        assert_eq!(priority_of('z'), Some(26));
+        assert_eq!(priority_of('A'), Some(27));
+        assert_eq!(priority_of('Z'), Some(52));
+        assert_eq!(priority_of('1'), None);
+        for priority in 1..=MAX_PRIORITY {
+            assert_eq!(item_of(priority).and_then(priority_of), Some(priority));
+        }
+        assert_eq!(item_of(0), None);
+        assert_eq!(item_of(53), None);
+    }
+
+    #[test]
+    fn should_intersect_and_unite_sets() {
+        let first: PrioritySet = "vJrwpWtwJgWr".parse().unwrap();
+        let second: PrioritySet = "hcsFMMfFFhFp".parse().unwrap();
+        let common = first.intersection(second);
+        assert_eq!(common.iter().collect::<Vec<_>>(), vec![16]);
+        assert_eq!(format!("{common:?}"), "{'p'}");
+
+        let all = first.union(second);
+        assert_eq!(all.len(), first.len() + second.len() - 1);
+        assert!(all.contains(priority_of('M').unwrap()));
+        assert!(!all.contains(priority_of('a').unwrap()));
+    }
+
+    #[test]
+    fn should_iterate_lowest_first() {
+        let set: PrioritySet = [52, 1, 27, 26, 1].into_iter().collect();
+        assert_eq!(set.iter().len(), 4);
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![1, 26, 27, 52]);
+        assert!(PrioritySet::new().is_empty());
+    }
+
+    #[test]
+    fn should_point_at_character_that_is_not_an_item() {
+        let error = "abc-d".parse::<PrioritySet>().unwrap_err();
+        assert_eq!(error.token, "-");
+        assert_eq!(error.column, 4);
+    }
+}
@@ -0,0 +1,541 @@
+//! An ordered set, the binary search tree day 3 first loaded rucksacks into.
+//! Day 3 itself has since moved on to
+//! [`PrioritySet`](super::priority_set::PrioritySet), but the tree stayed
+//! around for anything else that wants its values kept in order.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, RangeBounds},
+    str::FromStr,
+};
+
+use crate::ParseError;
+
+use super::priority_set::priority_of;
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone)]
+struct Node<T> {
+    value: T,
+    /// Nodes on the longest path down from this one, itself included.
+    height: u8,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// Set of values kept in order, in an AVL tree.
+///
+/// The heights of the two subtrees of every node differ by at most one, so
+/// however the values come in, sorted ones included, the tree stays about
+/// `log2(len)` deep, and looking a value up, inserting or removing one takes
+/// as many steps.
+#[derive(Clone)]
+pub struct MyBinarySearchTree<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> Default for MyBinarySearchTree<T> {
+    fn default() -> Self {
+        MyBinarySearchTree { root: None, len: 0 }
+    }
+}
+
+impl<T: Ord> MyBinarySearchTree<T> {
+    pub fn new() -> Self {
+        MyBinarySearchTree::default()
+    }
+
+    /// Adds `value`, returning whether it was new. A value already in the set
+    /// stays as it was.
+    pub fn insert(&mut self, value: T) -> bool {
+        let (root, inserted) = insert(self.root.take(), value);
+        self.root = Some(root);
+        if inserted {
+            self.len += 1;
+        }
+        inserted
+    }
+
+    /// Removes `value`, returning whether it was in the set.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Removes `value` and hands back the one the set held.
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let (root, removed) = remove(self.root.take(), value);
+        self.root = root;
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut node = &self.root;
+        while let Some(current) = node {
+            node = match value.cmp(&current.value) {
+                Ordering::Less => &current.left,
+                Ordering::Greater => &current.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some(&node.value)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some(&node.value)
+    }
+
+    /// The values, smallest first.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            stack: Vec::new(),
+            remaining: self.len,
+        };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    /// The values within `range`, smallest first, like
+    /// [`BTreeSet::range`](std::collections::BTreeSet::range).
+    pub fn range<'a, R>(&'a self, range: R) -> impl Iterator<Item = &'a T> + 'a
+    where
+        R: RangeBounds<T> + 'a,
+    {
+        // only the nodes from the start of the range onwards go on the stack,
+        // so iterating the rest of it goes on from the first value in range
+        let mut stack = Vec::new();
+        let mut node = self.root.as_deref();
+        while let Some(current) = node {
+            let after_start = match range.start_bound() {
+                Bound::Included(start) => current.value >= *start,
+                Bound::Excluded(start) => current.value > *start,
+                Bound::Unbounded => true,
+            };
+            if after_start {
+                stack.push(current);
+                node = current.left.as_deref();
+            } else {
+                node = current.right.as_deref();
+            }
+        }
+        let iter = Iter {
+            stack,
+            remaining: self.len,
+        };
+        iter.take_while(move |value| match range.end_bound() {
+            Bound::Included(end) => *value <= end,
+            Bound::Excluded(end) => *value < end,
+            Bound::Unbounded => true,
+        })
+    }
+}
+
+fn height<T>(link: &Link<T>) -> u8 {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+impl<T> Node<T> {
+    fn leaf(value: T) -> Self {
+        Node {
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn update_height(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+    }
+
+    /// How much taller the left subtree is than the right one.
+    fn balance_factor(&self) -> i16 {
+        i16::from(height(&self.left)) - i16::from(height(&self.right))
+    }
+}
+
+fn rotate_right<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut left = node.left.take().expect("rotating right takes a left child");
+    node.left = left.right.take();
+    node.update_height();
+    left.right = Some(node);
+    left.update_height();
+    left
+}
+
+fn rotate_left<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    let mut right = node
+        .right
+        .take()
+        .expect("rotating left takes a right child");
+    node.right = right.left.take();
+    node.update_height();
+    right.left = Some(node);
+    right.update_height();
+    right
+}
+
+/// Brings `node` back in balance after one of its subtrees grew or shrunk by
+/// one level.
+fn rebalance<T>(mut node: Box<Node<T>>) -> Box<Node<T>> {
+    node.update_height();
+    match node.balance_factor() {
+        2.. => {
+            if node
+                .left
+                .as_ref()
+                .is_some_and(|left| left.balance_factor() < 0)
+            {
+                node.left = node.left.take().map(rotate_left);
+            }
+            rotate_right(node)
+        }
+        ..=-2 => {
+            if node
+                .right
+                .as_ref()
+                .is_some_and(|right| right.balance_factor() > 0)
+            {
+                node.right = node.right.take().map(rotate_right);
+            }
+            rotate_left(node)
+        }
+        _ => node,
+    }
+}
+
+fn insert<T: Ord>(link: Link<T>, value: T) -> (Box<Node<T>>, bool) {
+    let Some(mut node) = link else {
+        return (Box::new(Node::leaf(value)), true);
+    };
+    let inserted = match value.cmp(&node.value) {
+        Ordering::Less => {
+            let (left, inserted) = insert(node.left.take(), value);
+            node.left = Some(left);
+            inserted
+        }
+        Ordering::Greater => {
+            let (right, inserted) = insert(node.right.take(), value);
+            node.right = Some(right);
+            inserted
+        }
+        Ordering::Equal => return (node, false),
+    };
+    (rebalance(node), inserted)
+}
+
+fn remove<T: Ord>(link: Link<T>, value: &T) -> (Link<T>, Option<T>) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let removed = match value.cmp(&node.value) {
+        Ordering::Less => {
+            let (left, removed) = remove(node.left.take(), value);
+            node.left = left;
+            removed
+        }
+        Ordering::Greater => {
+            let (right, removed) = remove(node.right.take(), value);
+            node.right = right;
+            removed
+        }
+        Ordering::Equal => {
+            let Node {
+                value, left, right, ..
+            } = *node;
+            let Some(right) = right else {
+                return (left, Some(value));
+            };
+            // the smallest value of the right subtree takes the place of the
+            // removed one
+            let (right, successor) = remove_min(right);
+            let node = Box::new(Node {
+                value: successor,
+                height: 1,
+                left,
+                right,
+            });
+            return (Some(rebalance(node)), Some(value));
+        }
+    };
+    (Some(rebalance(node)), removed)
+}
+
+fn remove_min<T>(mut node: Box<Node<T>>) -> (Link<T>, T) {
+    match node.left.take() {
+        None => (node.right.take(), node.value),
+        Some(left) => {
+            let (left, min) = remove_min(left);
+            node.left = left;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+/// Iterator over the values of a [`MyBinarySearchTree`], smallest first.
+pub struct Iter<'a, T> {
+    /// Nodes whose value and right subtree are still to come, the next one on
+    /// top.
+    stack: Vec<&'a Node<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left(&mut self, mut node: Option<&'a Node<T>>) {
+        while let Some(current) = node {
+            self.stack.push(current);
+            node = current.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.stack.len().min(self.remaining), Some(self.remaining))
+    }
+}
+
+/// Owning iterator over the values of a [`MyBinarySearchTree`], smallest
+/// first.
+pub struct IntoIter<T> {
+    stack: Vec<Box<Node<T>>>,
+    remaining: usize,
+}
+
+impl<T> IntoIter<T> {
+    fn push_left(&mut self, mut node: Link<T>) {
+        while let Some(mut current) = node {
+            node = current.left.take();
+            self.stack.push(current);
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node = self.stack.pop()?;
+        self.push_left(node.right.take());
+        self.remaining -= 1;
+        Some(node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for MyBinarySearchTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut iter = IntoIter {
+            stack: Vec::new(),
+            remaining: self.len,
+        };
+        iter.push_left(self.root);
+        iter
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a MyBinarySearchTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord> Extend<T> for MyBinarySearchTree<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MyBinarySearchTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = MyBinarySearchTree::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+/// Two trees are equal when they hold the same values, however differently
+/// they got balanced along the way.
+impl<T: Ord> PartialEq for MyBinarySearchTree<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Ord> Eq for MyBinarySearchTree<T> {}
+
+impl<T: Ord + fmt::Debug> fmt::Debug for MyBinarySearchTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Reads the priorities of the items of a rucksack or compartment, like
+/// `vJrwpWtwJgWr`.
+impl FromStr for MyBinarySearchTree<u32> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(offset, item)| {
+                priority_of(item).map(u32::from).ok_or_else(|| {
+                    ParseError::new(&item.to_string(), "an item, a to z or A to Z").shifted(offset)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// Checks the order and balance of every node, returning the height of
+    /// `link` and how many values it holds.
+    fn check_invariants<T: Ord>(link: &Link<T>) -> (u8, usize) {
+        let Some(node) = link else {
+            return (0, 0);
+        };
+        let (left_height, left_len) = check_invariants(&node.left);
+        let (right_height, right_len) = check_invariants(&node.right);
+        assert!(node
+            .left
+            .as_ref()
+            .is_none_or(|left| left.value < node.value));
+        assert!(node
+            .right
+            .as_ref()
+            .is_none_or(|right| right.value > node.value));
+        assert!(left_height.abs_diff(right_height) <= 1, "out of balance");
+        assert_eq!(node.height, 1 + left_height.max(right_height));
+        (node.height, 1 + left_len + right_len)
+    }
+
+    /// Numbers that look random enough, always the same ones for a seed.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
+
+    #[test]
+    fn should_behave_like_btree_set() {
+        for seed in 1..=50 {
+            let mut random = XorShift(seed);
+            let mut tree = MyBinarySearchTree::new();
+            let mut expected = BTreeSet::new();
+            // a small range of values, so removals and duplicates hit often
+            let bound = 10 + seed * 4;
+
+            for _ in 0..400 {
+                let value = random.below(bound);
+                match random.below(4) {
+                    0 | 1 => assert_eq!(tree.insert(value), expected.insert(value)),
+                    2 => assert_eq!(tree.remove(&value), expected.remove(&value)),
+                    _ => assert_eq!(tree.contains(&value), expected.contains(&value)),
+                }
+                assert_eq!(tree.len(), expected.len());
+            }
+
+            let (_, len) = check_invariants(&tree.root);
+            assert_eq!(len, expected.len());
+            assert!(tree.iter().eq(expected.iter()));
+            assert_eq!(tree.min(), expected.first());
+            assert_eq!(tree.max(), expected.last());
+
+            let (first, second) = (random.below(bound), random.below(bound));
+            let (start, end) = (first.min(second), first.max(second));
+            assert!(tree.range(start..end).eq(expected.range(start..end)));
+            assert!(tree.range(start..).eq(expected.range(start..)));
+            assert!(tree.range(..=end).eq(expected.range(..=end)));
+            assert!(tree.into_iter().eq(expected.into_iter()));
+        }
+    }
+
+    #[test]
+    fn should_stay_shallow_on_sorted_values() {
+        let tree: MyBinarySearchTree<u32> = (0..100_000).collect();
+        let (height, len) = check_invariants(&tree.root);
+        assert_eq!(len, 100_000);
+        // an AVL tree is never more than 1.44 times as deep as a perfectly
+        // balanced one, which would be 17 levels deep
+        assert!(height <= 24, "{height} levels deep");
+    }
+
+    #[test]
+    fn should_tell_whether_values_were_new_or_present() {
+        let mut tree = MyBinarySearchTree::new();
+        assert!(tree.insert("b"));
+        assert!(tree.insert("a"));
+        assert!(!tree.insert("b"));
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.take(&"a"), Some("a"));
+        assert!(!tree.remove(&"a"));
+        assert_eq!(format!("{tree:?}"), "{\"b\"}");
+        assert!(MyBinarySearchTree::<u8>::new().min().is_none());
+    }
+
+    #[test]
+    fn should_read_priorities_of_items() {
+        let tree: MyBinarySearchTree<u32> = "vJrwpWtwJgWr".parse().unwrap();
+        assert!(tree.contains(&16));
+        assert_eq!(tree.len(), 8);
+        let error = "ab1".parse::<MyBinarySearchTree<u32>>().unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (3, "1"));
+    }
+}
@@ -1,118 +1,147 @@
-use std::{collections::HashMap, io::BufRead, str::FromStr};
+use crate::{debug, trace, Error, Input, ParseError};
 
-use crate::read_file;
+use super::{
+    common_item::CommonItem,
+    priority_set::{item_of, PrioritySet},
+};
 
-use super::first_part::{get_priority_of_item, MyBinarySearchTree};
+/// Elves walking together, as the puzzle groups them.
+pub const GROUP_SIZE: usize = 3;
 
-#[derive(Debug)]
-struct CommonItem {
-    inner: HashMap<u8, u8>,
+pub fn sum_of_badge_priorities(input: Input) -> Result<String, Error> {
+    sum_of_badge_priorities_in_groups_of(input, GROUP_SIZE)
 }
 
-impl CommonItem {
-    fn new() -> Self {
-        CommonItem {
-            inner: HashMap::new(),
-        }
-    }
-
-    fn push(&mut self, data: u8) {
-        if self.inner.get(&data).is_none() {
-            self.inner.insert(data, 1);
-        }
-
-        let value = self.inner.get(&data).unwrap();
-        self.inner.insert(data, value + 1);
+/// Like the puzzle, with the elves walking in groups of `group_size` instead
+/// of three, which has to be at least one.
+pub fn sum_of_badge_priorities_in_groups_of(
+    input: Input,
+    group_size: usize,
+) -> Result<String, Error> {
+    if group_size == 0 {
+        return Err(Error::InvalidOption {
+            name: "size".to_string(),
+            message: "groups need at least one elf".to_string(),
+        });
     }
-}
-
-#[allow(dead_code)]
-fn sum_of_badge_priorities(filename: &str) -> String {
-    let reader = read_file(filename);
-
     let mut total_count: u32 = 0;
-    let mut elf_bags: Vec<String> = Vec::new();
-    let mut elf_counter = 0;
-
-    for line in reader.lines() {
-        let line_data = line.unwrap();
-        println!("Reading: {}", line_data);
-        elf_bags.push(line_data);
-        elf_counter += 1;
-
-        if elf_counter == 3 {
-            println!("Calculating boundary for badge");
-            let badge = find_badge_from_bags(&elf_bags[..]);
-            println!("Counter: {} --- Attempt: {}", total_count, badge);
-            total_count += u32::try_from(badge).unwrap();
-            elf_counter = 0;
+    let mut elf_bags: Vec<PrioritySet> = Vec::with_capacity(group_size);
+    let mut rucksacks = 0;
+
+    for (line_index, line) in input.lines().enumerate() {
+        let line_data = line?;
+        trace!("Reading: {}", line_data);
+        let bag = line_data
+            .parse()
+            .map_err(|error: ParseError| error.on_line(line_index + 1))?;
+        elf_bags.push(bag);
+        rucksacks += 1;
+
+        if elf_bags.len() == group_size {
+            debug!("Calculating boundary for badge");
+            let badge = find_badge_from_bags(&elf_bags, line_index + 2 - group_size)?;
+            debug!("Counter: {} --- Attempt: {}", total_count, badge);
+            total_count += u32::from(badge);
             elf_bags.clear();
         }
     }
 
-    total_count.to_string()
-}
-
-#[allow(dead_code)]
-fn find_badge_from_bags(elf_bags: &[String]) -> u8 {
-    let mut iter = elf_bags.iter();
-    let first_bag = iter.next().unwrap();
-    let first_bag_to_represent_as_tree = MyBinarySearchTree::from_str(first_bag).unwrap();
-    println!("Tree: {:?}", first_bag_to_represent_as_tree);
-    let mut common_item_manager = CommonItem::new();
-
-    for bag in iter {
-        let mut already_added: Vec<u32> = Vec::new();
-
-        for i in bag.chars() {
-            let item_priority = u32::try_from(get_priority_of_item(i)).unwrap();
-            if first_bag_to_represent_as_tree.does_item_exist(item_priority) {
-                println!("Found potential: {}", item_priority);
-                if already_added.contains(&item_priority) {
-                    println!("Redundant, ignoring");
-                    continue;
-                }
-                common_item_manager.push(u8::try_from(item_priority).unwrap());
-                already_added.push(item_priority);
-            }
-        }
+    if !elf_bags.is_empty() {
+        return Err(Error::Unsolvable {
+            message: format!(
+                "{rucksacks} rucksacks do not split into groups of {group_size}, \
+                 leaving the {} from line {} without a group",
+                elf_bags.len(),
+                rucksacks + 1 - elf_bags.len()
+            ),
+        });
     }
 
-    println!("My boi status pls: {:?}", common_item_manager);
+    Ok(total_count.to_string())
+}
 
-    for (k, v) in common_item_manager.inner {
-        return match v == 3 {
-            true => k,
-            false => continue,
-        };
+/// Priority of the one item every one of `elf_bags` holds, the badge of the
+/// group whose first rucksack is on line `first_line`.
+fn find_badge_from_bags(elf_bags: &[PrioritySet], first_line: usize) -> Result<u8, Error> {
+    // every bag counts an item once, however many of it the bag holds, so the
+    // items in every bag are the ones counted once per bag
+    let common_item_manager: CommonItem<u8> = elf_bags.iter().flat_map(PrioritySet::iter).collect();
+    trace!("Items counted: {:?}", common_item_manager);
+    let common: Vec<u8> = common_item_manager
+        .counted(elf_bags.len())
+        .copied()
+        .collect();
+    let lines = format!("lines {first_line} to {}", first_line + elf_bags.len() - 1);
+
+    match common[..] {
+        [badge] => Ok(badge),
+        [] => Err(Error::Unsolvable {
+            message: format!("rucksacks on {lines} have no item in common"),
+        }),
+        _ => {
+            let items: Vec<String> = common
+                .iter()
+                .filter_map(|&priority| item_of(priority))
+                .map(String::from)
+                .collect();
+            Err(Error::Unsolvable {
+                message: format!(
+                    "rucksacks on {lines} have {} items in common, {}, instead of one badge",
+                    items.len(),
+                    items.join(", ")
+                ),
+            })
+        }
     }
-
-    panic!("Shouldn't reach here")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::sum_of_badge_priorities;
+    use super::*;
+
+    #[test]
+    fn should_find_badges_in_groups_of_any_size() {
+        let input = Input::from_text("abX\ncdX\nefY\nghY\n");
+        assert_eq!(
+            sum_of_badge_priorities_in_groups_of(input, 2).unwrap(),
+            (50 + 51).to_string()
+        );
+    }
 
     #[test]
-    fn should_get_sum_of_badge_priorities_of_a_group() {
-        let file_path = "./inputs/day_3/mocks/example.txt";
-        let result = sum_of_badge_priorities(file_path);
-        assert_eq!(result, "70")
+    fn should_reject_groups_without_elves() {
+        let input = Input::from_text("abX\ncdX\n");
+        let error = sum_of_badge_priorities_in_groups_of(input, 0).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "option size: groups need at least one elf"
+        );
     }
 
     #[test]
-    fn should_get_sum_of_badge_priorities_of_a_group_custom() {
-        let file_path = "./inputs/day_3/mocks/my_example.txt";
-        let result = sum_of_badge_priorities(file_path);
-        assert_eq!(result, "3")
+    fn should_report_rucksacks_left_over() {
+        let input = Input::from_text("aX\nbX\ncX\ndX\n");
+        let error = sum_of_badge_priorities_in_groups_of(input, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "4 rucksacks do not split into groups of 3, leaving the 1 from line 4 without a group"
+        );
     }
 
     #[test]
-    #[ignore]
-    fn actual_test() {
-        let file_path = "./inputs/day_3/aoc_input.txt";
-        let result = sum_of_badge_priorities(file_path);
-        assert_eq!(result, "70")
+    fn should_report_group_without_one_badge() {
+        let input = Input::from_text("ab\nbc\nca\naBC\nBCd\nBCe\n");
+        let error = sum_of_badge_priorities_in_groups_of(input, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "rucksacks on lines 1 to 3 have no item in common"
+        );
+
+        let input = Input::from_text("aBCb\nBCd\nBCe\n");
+        let error = sum_of_badge_priorities_in_groups_of(input, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "rucksacks on lines 1 to 3 have 2 items in common, B, C, instead of one badge"
+        );
     }
 }
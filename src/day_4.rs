@@ -1,2 +1,73 @@
+pub mod coverage;
 mod first_part;
+pub mod interval;
+pub mod interval_set;
 mod second_part;
+
+use crate::{mode, Error, Input, Mode, Options, Parsed, Part, Solver};
+
+use coverage::Camp;
+use first_part::AssignmentPair;
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::number_of_assignment_pairs_fully_containing_other(input)
+            .map(|answer| answer.to_string())
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::find_number_of_pairs_overlapping(input).map(|answer| answer.to_string())
+    }
+
+    fn parse(&self, input: Input) -> Result<Parsed, Error> {
+        Ok(Box::new(first_part::read_pairs(input)?))
+    }
+
+    fn solve_parsed(&self, part: Part, parsed: Parsed) -> Result<String, Error> {
+        let pairs = parsed
+            .downcast::<Vec<(AssignmentPair, AssignmentPair)>>()
+            .expect("parsed by Day4::parse");
+        let answer = match part {
+            Part::One => first_part::count_pairs_fully_containing_other(&pairs),
+            Part::Two => second_part::count_pairs_overlapping(&pairs),
+        };
+        Ok(answer.to_string())
+    }
+
+    fn modes(&self) -> &'static [Mode] {
+        &[Mode {
+            name: "coverage",
+            about: "sections the whole camp covers and misses (within=2-9, section=N)",
+        }]
+    }
+
+    fn run_mode(&self, mode: &str, options: &Options, input: Input) -> Result<String, Error> {
+        match mode {
+            "coverage" => {
+                let within = options.parse("within")?;
+                let section = options.parse("section")?;
+                Ok(Camp::from_input(input)?.report(within, section))
+            }
+            _ => Err(mode::unknown(mode, self.modes())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reject_within_that_is_not_a_range() {
+        let options: Options = [("within".to_string(), "9-2".to_string())]
+            .into_iter()
+            .collect();
+        let result = Day4.run_mode("coverage", &options, Input::from_text("2-4,6-8\n"));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "option within: \"9-2\": column 1: expected a range not ending before it starts, found \"9-2\""
+        );
+    }
+}
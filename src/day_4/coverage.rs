@@ -0,0 +1,154 @@
+//! The whole camp at once: which sections the elves cover between them,
+//! which they leave out, and who cleans a given section.
+
+use crate::{Error, Input};
+
+use super::{
+    first_part::{AssignmentPair, SectionId},
+    interval::Interval,
+    interval_set::IntervalSet,
+};
+
+/// The sections of one elf, and where in the input they were assigned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+    pub line: usize,
+    /// `1` for the first elf of the pair on the line, `2` for the second.
+    pub elf: usize,
+    pub sections: Interval<SectionId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Camp {
+    pub assignments: Vec<Assignment>,
+    pub coverage: IntervalSet<SectionId>,
+}
+
+impl Camp {
+    pub fn from_input(input: Input) -> Result<Self, Error> {
+        let mut assignments = Vec::new();
+        for (line_index, line) in input.lines().enumerate() {
+            let line_data = line?;
+            let (first, second) = AssignmentPair::from_line(&line_data)
+                .map_err(|error| error.on_line(line_index + 1))?;
+            for (elf, pair) in [(1, first), (2, second)] {
+                assignments.push(Assignment {
+                    line: line_index + 1,
+                    elf,
+                    sections: pair.sections(),
+                });
+            }
+        }
+        let coverage = assignments
+            .iter()
+            .map(|assignment| assignment.sections)
+            .collect();
+        Ok(Camp {
+            assignments,
+            coverage,
+        })
+    }
+
+    /// Elves whose sections include `section`, in the order of the input.
+    pub fn covering(&self, section: SectionId) -> impl Iterator<Item = &Assignment> {
+        self.assignments
+            .iter()
+            .filter(move |assignment| assignment.sections.contains(section))
+    }
+
+    /// Merged sections, how many there are, and the gaps `within` them,
+    /// followed by who covers `section` when one is asked about. Gaps are
+    /// looked for between the lowest and highest section assigned unless
+    /// `within` says otherwise.
+    pub fn report(
+        &self,
+        within: Option<Interval<SectionId>>,
+        section: Option<SectionId>,
+    ) -> String {
+        let mut lines = vec![
+            format!("assignments: {}", self.assignments.len()),
+            format!(
+                "covered: {} ({} sections)",
+                self.coverage,
+                self.coverage.covered()
+            ),
+        ];
+
+        if let Some(within) = within.or_else(|| self.coverage.bounds()) {
+            let gaps = IntervalSet::from_iter(self.coverage.gaps(within));
+            if gaps.is_empty() {
+                lines.push(format!("gaps within {within}: none"));
+            } else {
+                lines.push(format!(
+                    "gaps within {within}: {} ({} sections)",
+                    gaps,
+                    gaps.covered()
+                ));
+            }
+        }
+
+        if let Some(section) = section {
+            let elves: Vec<String> = self
+                .covering(section)
+                .map(|assignment| {
+                    format!(
+                        "elf {} of line {} ({})",
+                        assignment.elf, assignment.line, assignment.sections
+                    )
+                })
+                .collect();
+            if elves.is_empty() {
+                lines.push(format!("section {section} is covered by no elf"));
+            } else {
+                lines.push(format!(
+                    "section {section} is covered by {}",
+                    elves.join(", ")
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Camp {
+        let input = Input::from_path("./inputs/day_4/mocks/example.txt").unwrap();
+        Camp::from_input(input).unwrap()
+    }
+
+    #[test]
+    fn should_merge_the_whole_camp() {
+        let camp = example();
+        assert_eq!(camp.assignments.len(), 12);
+        assert_eq!(camp.coverage.to_string(), "2-9");
+        assert_eq!(camp.coverage.covered(), 8);
+    }
+
+    #[test]
+    fn should_find_elves_covering_a_section() {
+        let camp = example();
+        let elves: Vec<(usize, usize)> = camp
+            .covering(3)
+            .map(|assignment| (assignment.line, assignment.elf))
+            .collect();
+        assert_eq!(elves, vec![(1, 1), (2, 1), (4, 1), (4, 2), (6, 1)]);
+        assert_eq!(camp.covering(1).count(), 0);
+    }
+
+    #[test]
+    fn should_report_gaps_and_covering_elves() {
+        let camp = example();
+        let within = "1-10".parse().unwrap();
+        assert_eq!(
+            camp.report(Some(within), Some(9)),
+            "assignments: 12\n\
+             covered: 2-9 (8 sections)\n\
+             gaps within 1-10: 1-1, 10-10 (2 sections)\n\
+             section 9 is covered by elf 2 of line 3 (7-9)"
+        );
+        assert!(camp.report(None, None).ends_with("gaps within 2-9: none"));
+    }
+}
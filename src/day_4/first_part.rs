@@ -1,61 +1,86 @@
-use std::{io::BufRead, str::FromStr};
+use std::str::FromStr;
 
-use crate::read_file;
+use crate::{debug, trace, Error, Input, ParseError};
 
-#[derive(Debug)]
+use super::interval::Interval;
+
+/// Section ids as wide as any input is going to need.
+pub type SectionId = u32;
+
+/// The sections assigned to one elf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AssignmentPair {
-    start: u8,
-    end: u8,
+    sections: Interval<SectionId>,
 }
 
 impl AssignmentPair {
+    pub fn sections(&self) -> Interval<SectionId> {
+        self.sections
+    }
+
     pub fn is_a_range_subset_of_other(first_range: &Self, second_range: &Self) -> bool {
-        (first_range.start <= second_range.start && second_range.end <= first_range.end)
-            || (first_range.start >= second_range.start && second_range.end >= first_range.end)
+        first_range
+            .sections
+            .contains_interval(&second_range.sections)
+            || second_range
+                .sections
+                .contains_interval(&first_range.sections)
     }
 
     pub fn has_overlap(first_range: &Self, second_range: &Self) -> bool {
-        // Now this was interesting, instead of matching all cases that DO overlap,
-        // It ended up being easier to just check for cases that DO NOT overlap, and
-        // that case was super concise.
-        !(first_range.end < second_range.start || first_range.start > second_range.end)
+        first_range.sections.overlaps(&second_range.sections)
+    }
+
+    /// Parses a whole line of the puzzle, i.e. the two comma separated ranges
+    /// assigned to a pair of elves.
+    pub fn from_line(s: &str) -> Result<(Self, Self), ParseError> {
+        let (first_range, second_range) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(s, "two ranges separated by a comma"))?;
+        let first_section_range = AssignmentPair::from_str(first_range)?;
+        let second_section_range = AssignmentPair::from_str(second_range)
+            .map_err(|error| error.shifted(first_range.len() + 1))?;
+        Ok((first_section_range, second_section_range))
+    }
+}
+
+impl From<Interval<SectionId>> for AssignmentPair {
+    fn from(sections: Interval<SectionId>) -> Self {
+        AssignmentPair { sections }
     }
 }
 
 impl FromStr for AssignmentPair {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let range_split: Vec<&str> = s.split('-').collect();
-        debug_assert!(range_split.len() == 2);
         Ok(AssignmentPair {
-            start: u8::from_str(range_split[0]).unwrap(),
-            end: u8::from_str(range_split[1]).unwrap(),
+            sections: s.parse()?,
         })
     }
 }
 
-#[allow(dead_code)]
-fn number_of_assignment_pairs_fully_containing_other(filename: &str) -> u32 {
-    let reader = read_file(filename);
+/// Reads the two assignments on every line of `input`.
+pub fn read_pairs(input: Input) -> Result<Vec<(AssignmentPair, AssignmentPair)>, Error> {
+    let mut pairs = Vec::new();
+    for (line_index, line) in input.lines().enumerate() {
+        let line_data = line?;
+        pairs.push(
+            AssignmentPair::from_line(&line_data).map_err(|error| error.on_line(line_index + 1))?,
+        );
+    }
+    Ok(pairs)
+}
 
+pub fn count_pairs_fully_containing_other(pairs: &[(AssignmentPair, AssignmentPair)]) -> u32 {
     let mut number_of_pairs_completely_overlapping_the_other: u32 = 0;
 
-    for line in reader.lines() {
-        let line_data = line.unwrap();
-
-        let pair: Vec<&str> = line_data.split(',').collect();
-        debug_assert!(pair.len() == 2);
-        println!("First section range: {}", pair[0]);
-        println!("Second section range: {}", pair[1]);
-
-        let first_section_range = AssignmentPair::from_str(pair[0]).unwrap();
-        let second_section_range = AssignmentPair::from_str(pair[1]).unwrap();
-        println!("First section range: {:?}", first_section_range);
-        println!("Second section range: {:?}", second_section_range);
+    for (first_section_range, second_section_range) in pairs {
+        trace!("First section range: {:?}", first_section_range);
+        trace!("Second section range: {:?}", second_section_range);
         let is_a_pair_subset_of_other =
-            AssignmentPair::is_a_range_subset_of_other(&first_section_range, &second_section_range);
-        println!("Is subset: {}", is_a_pair_subset_of_other);
+            AssignmentPair::is_a_range_subset_of_other(first_section_range, second_section_range);
+        debug!("Is subset: {}", is_a_pair_subset_of_other);
         if is_a_pair_subset_of_other {
             number_of_pairs_completely_overlapping_the_other += 1
         }
@@ -64,22 +89,45 @@ fn number_of_assignment_pairs_fully_containing_other(filename: &str) -> u32 {
     number_of_pairs_completely_overlapping_the_other
 }
 
+pub fn number_of_assignment_pairs_fully_containing_other(input: Input) -> Result<u32, Error> {
+    Ok(count_pairs_fully_containing_other(&read_pairs(input)?))
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::{Error, Input};
+
     use super::number_of_assignment_pairs_fully_containing_other;
 
     #[test]
-    fn should_find_number_of_pairs_being_subset_example() {
-        let file_path = "./inputs/day_4/mocks/example.txt";
-        let result = number_of_assignment_pairs_fully_containing_other(file_path);
-        assert_eq!(result, 2);
+    fn should_report_section_id_that_does_not_fit() {
+        let input = Input::from_text("2-4,6-8\n2-5000000000,4-5\n");
+        match number_of_assignment_pairs_fully_containing_other(input) {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (2, 3));
+                assert_eq!(error.token, "5000000000");
+                assert_eq!(error.expected, "a number from 0 to 4294967295");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
     }
 
     #[test]
-    #[ignore]
-    fn actual_test() {
-        let file_path = "./inputs/day_4/aoc_input.txt";
-        let result = number_of_assignment_pairs_fully_containing_other(file_path);
-        assert_eq!(result, 2);
+    fn should_take_section_ids_past_255() {
+        let input = Input::from_text("2-300,4-256\n300-400,1-299\n");
+        let result = number_of_assignment_pairs_fully_containing_other(input).unwrap();
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn should_report_range_ending_before_it_starts() {
+        let input = Input::from_text("2-4,8-6\n");
+        match number_of_assignment_pairs_fully_containing_other(input) {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (1, 5));
+                assert_eq!(error.token, "8-6");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
     }
 }
@@ -0,0 +1,199 @@
+use std::{fmt, str::FromStr};
+
+use num_traits::PrimInt;
+
+use crate::ParseError;
+
+/// Closed range of integers, `start` and `end` included, like the sections
+/// `2-4` an elf gets assigned.
+///
+/// Works with any primitive integer, so inputs are only limited by the type
+/// picked for them, and `start` is never past `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: PrimInt + fmt::Display> Interval<T> {
+    pub fn new(start: T, end: T) -> Result<Self, String> {
+        if start > end {
+            return Err(format!("{start}-{end} ends before it starts"));
+        }
+        Ok(Interval { start, end })
+    }
+
+    /// The interval of `value` alone.
+    pub fn single(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Number of integers within, which `0-255` of `u8` already needs more
+    /// than a `u8` for. Saturates for the whole of `u128`, the one interval
+    /// even a `u128` cannot count.
+    pub fn len(&self) -> u128 {
+        let span = match (self.start.to_i128(), self.end.to_i128()) {
+            (Some(start), Some(end)) => end.abs_diff(start),
+            // past i128, so unsigned, and both fit a u128
+            _ => self.end.to_u128().unwrap_or(u128::MAX) - self.start.to_u128().unwrap_or(0),
+        };
+        span.saturating_add(1)
+    }
+
+    /// Never true, `start` and `end` are always within, but expected next to
+    /// [`Interval::len`].
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether every integer of `other` is within this one too.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the two share at least one integer.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        // easier said the other way around: two intervals do not overlap when
+        // one ends before the other starts
+        !(self.end < other.start || other.end < self.start)
+    }
+
+    /// Integers within both, `None` when they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other).then(|| Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        })
+    }
+
+    /// Whether the two overlap or one starts right after the other ends, so
+    /// that together they make up one interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        let right_after =
+            |before: &Self, after: &Self| before.end.checked_add(&T::one()) == Some(after.start);
+        self.overlaps(other) || right_after(self, other) || right_after(other, self)
+    }
+
+    /// The one interval both make up together, `None` when there is a gap
+    /// between them.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| Interval {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Reads an interval written like `2-4`.
+impl<T: PrimInt + FromStr + fmt::Display> FromStr for Interval<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // a `-` right at the start is the sign of a negative start, not the
+        // one between start and end
+        let separator = s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-')
+            .map(|(offset, _)| offset)
+            .ok_or_else(|| ParseError::new(s, "a range like `2-4`"))?;
+        let (start, end) = (&s[..separator], &s[separator + 1..]);
+        let bound = |id: &str, offset: usize| {
+            T::from_str(id).map_err(|_| {
+                let expected = format!("a number from {} to {}", T::min_value(), T::max_value());
+                ParseError::new(id, expected).shifted(offset)
+            })
+        };
+        let (start, end) = (bound(start, 0)?, bound(end, separator + 1)?);
+        Interval::new(start, end)
+            .map_err(|_| ParseError::new(s, "a range not ending before it starts"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i64, end: i64) -> Interval<i64> {
+        Interval::new(start, end).unwrap()
+    }
+
+    #[test]
+    fn should_reject_interval_ending_before_it_starts() {
+        assert_eq!(
+            Interval::new(5u8, 4),
+            Err("5-4 ends before it starts".to_string())
+        );
+        let error = "5-4".parse::<Interval<u8>>().unwrap_err();
+        assert_eq!(error.expected, "a range not ending before it starts");
+    }
+
+    #[test]
+    fn should_compare_intervals() {
+        let (outer, inner) = (interval(2, 8), interval(3, 7));
+        assert!(outer.contains_interval(&inner));
+        assert!(!inner.contains_interval(&outer));
+        assert!(outer.contains(2) && outer.contains(8) && !outer.contains(9));
+
+        assert!(interval(5, 7).overlaps(&interval(7, 9)));
+        assert!(!interval(2, 4).overlaps(&interval(6, 8)));
+        assert_eq!(
+            interval(2, 6).intersection(&interval(4, 8)),
+            Some(interval(4, 6))
+        );
+        assert_eq!(interval(2, 3).intersection(&interval(4, 8)), None);
+    }
+
+    #[test]
+    fn should_unite_touching_intervals_only() {
+        assert_eq!(interval(2, 3).union(&interval(4, 8)), Some(interval(2, 8)));
+        assert_eq!(interval(4, 8).union(&interval(2, 5)), Some(interval(2, 8)));
+        assert_eq!(interval(2, 3).union(&interval(5, 8)), None);
+        let top = Interval::new(250u8, 255).unwrap();
+        assert_eq!(top.union(&Interval::new(0, 3).unwrap()), None);
+    }
+
+    #[test]
+    fn should_count_integers_past_the_width_of_the_type() {
+        assert_eq!(Interval::new(0u8, 255).unwrap().len(), 256);
+        assert_eq!(Interval::new(i8::MIN, i8::MAX).unwrap().len(), 256);
+        assert_eq!(Interval::single(7u64).len(), 1);
+        assert_eq!(
+            Interval::new(0, u128::MAX).unwrap().len(),
+            u128::MAX,
+            "saturates"
+        );
+        assert_eq!(Interval::new(1, u128::MAX).unwrap().len(), u128::MAX);
+    }
+
+    #[test]
+    fn should_parse_any_width() {
+        assert_eq!("-5--2".parse::<Interval<i64>>(), Ok(interval(-5, -2)));
+        let wide: Interval<u64> = "2-5000000000".parse().unwrap();
+        assert_eq!(wide.end(), 5_000_000_000);
+        let error = "2-300".parse::<Interval<u8>>().unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (3, "300"));
+        assert_eq!(error.expected, "a number from 0 to 255");
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+
+use num_traits::PrimInt;
+
+use super::interval::Interval;
+
+/// Integers covered by any number of intervals, kept as the fewest intervals
+/// that cover them: sorted, and with a gap between any two of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: PrimInt + fmt::Display> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// Adds the integers of `interval`, merging it with every interval it
+    /// overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // the intervals entirely before the new one, with a gap in between,
+        // are the ones left alone at the start
+        let first = self
+            .intervals
+            .partition_point(|kept| kept.end() < interval.start() && !kept.touches(&interval));
+        let mut merged = interval;
+        let mut last = first;
+        while let Some(kept) = self.intervals.get(last) {
+            match merged.union(kept) {
+                Some(union) => merged = union,
+                None => break,
+            }
+            last += 1;
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// The merged intervals, lowest first.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end() < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Number of integers covered.
+    pub fn covered(&self) -> u128 {
+        self.intervals.iter().fold(0, |covered: u128, interval| {
+            covered.saturating_add(interval.len())
+        })
+    }
+
+    /// The smallest interval holding every covered integer, `None` when
+    /// nothing is.
+    pub fn bounds(&self) -> Option<Interval<T>> {
+        let (first, last) = (self.intervals.first()?, self.intervals.last()?);
+        Interval::new(first.start(), last.end()).ok()
+    }
+
+    /// Integers of `within` no interval covers, as intervals, lowest first.
+    pub fn gaps(&self, within: Interval<T>) -> Vec<Interval<T>> {
+        let mut gaps = Vec::new();
+        // first integer not known to be covered yet, `None` once past the
+        // largest integer of `T`
+        let mut next = Some(within.start());
+        for interval in self
+            .intervals
+            .iter()
+            .filter(|interval| interval.overlaps(&within))
+        {
+            let Some(start) = next else { break };
+            if start < interval.start() {
+                gaps.extend(Interval::new(start, interval.start() - T::one()).ok());
+            }
+            next = interval.end().checked_add(&T::one());
+        }
+        if let Some(start) = next.filter(|&start| start <= within.end()) {
+            gaps.extend(Interval::new(start, within.end()).ok());
+        }
+        gaps
+    }
+}
+
+impl<T: PrimInt + fmt::Display> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: PrimInt + fmt::Display> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Written like `2-4, 6-8`.
+impl<T: fmt::Display> fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, interval) in self.intervals.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{interval}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(u8, u8)]) -> IntervalSet<u8> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_merge_overlapping_and_touching_intervals() {
+        assert_eq!(set(&[(6, 8), (2, 4), (5, 5)]).to_string(), "2-8");
+        assert_eq!(set(&[(2, 3), (7, 9), (5, 5)]).to_string(), "2-3, 5-5, 7-9");
+        assert_eq!(set(&[(2, 3), (7, 9), (1, 8)]).to_string(), "1-9");
+        assert_eq!(
+            set(&[(250, 255), (0, 3), (4, 4)]).to_string(),
+            "0-4, 250-255"
+        );
+    }
+
+    #[test]
+    fn should_count_covered_integers() {
+        let sections = set(&[(2, 4), (6, 8), (3, 7)]);
+        assert_eq!(sections.covered(), 7);
+        assert!(sections.contains(2) && sections.contains(8));
+        assert!(!sections.contains(1) && !sections.contains(9));
+        assert_eq!(set(&[(0, 255)]).covered(), 256);
+        assert_eq!(IntervalSet::<u8>::new().covered(), 0);
+    }
+
+    #[test]
+    fn should_list_gaps_within_bounds() {
+        let sections = set(&[(2, 3), (6, 6), (8, 9)]);
+        let gaps = |start, end| {
+            let gaps = sections.gaps(Interval::new(start, end).unwrap());
+            IntervalSet::from_iter(gaps).to_string()
+        };
+        assert_eq!(gaps(1, 10), "1-1, 4-5, 7-7, 10-10");
+        assert_eq!(gaps(3, 8), "4-5, 7-7");
+        assert_eq!(gaps(2, 3), "");
+        assert_eq!(
+            set(&[(0, 255)]).gaps(Interval::new(0, 255).unwrap()),
+            vec![]
+        );
+        assert_eq!(
+            set(&[(0, 1)]).gaps(Interval::new(0, 255).unwrap()),
+            vec![Interval::new(2, 255).unwrap()]
+        );
+    }
+}
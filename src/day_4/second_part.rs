@@ -1,27 +1,17 @@
-use std::io::BufRead;
-use std::str::FromStr;
-
-use crate::{day_4::first_part::AssignmentPair, read_file};
-
-#[allow(dead_code)]
-fn find_number_of_pairs_overlapping(filename: &str) -> u32 {
-    let reader = read_file(filename);
+use crate::{
+    day_4::first_part::{self, AssignmentPair},
+    debug, trace, Error, Input,
+};
 
+pub fn count_pairs_overlapping(pairs: &[(AssignmentPair, AssignmentPair)]) -> u32 {
     let mut number_of_overlapping_pairs: u32 = 0;
 
-    for line in reader.lines() {
-        let line_data = line.unwrap();
-
-        let pair: Vec<&str> = line_data.split(',').collect();
-        debug_assert!(pair.len() == 2);
-        println!("First section range: {}", pair[0]);
-        println!("Second section range: {}", pair[1]);
-
-        let first_section_range = AssignmentPair::from_str(pair[0]).unwrap();
-        let second_section_range = AssignmentPair::from_str(pair[1]).unwrap();
+    for (first_section_range, second_section_range) in pairs {
+        trace!("First section range: {:?}", first_section_range);
+        trace!("Second section range: {:?}", second_section_range);
         let has_overlapping_pairs =
-            AssignmentPair::has_overlap(&first_section_range, &second_section_range);
-        println!("Has overlap: {}", has_overlapping_pairs);
+            AssignmentPair::has_overlap(first_section_range, second_section_range);
+        debug!("Has overlap: {}", has_overlapping_pairs);
         if has_overlapping_pairs {
             number_of_overlapping_pairs += 1
         }
@@ -30,22 +20,6 @@ fn find_number_of_pairs_overlapping(filename: &str) -> u32 {
     number_of_overlapping_pairs
 }
 
-#[cfg(test)]
-mod tests {
-    use super::find_number_of_pairs_overlapping;
-
-    #[test]
-    fn should_find_number_of_pairs_overlapping() {
-        let file_path = "./inputs/day_4/mocks/example.txt";
-        let result = find_number_of_pairs_overlapping(file_path);
-        assert_eq!(result, 4);
-    }
-
-    #[test]
-    #[ignore]
-    fn actual_test() {
-        let file_path = "./inputs/day_4/aoc_input.txt";
-        let result = find_number_of_pairs_overlapping(file_path);
-        assert_eq!(result, 2);
-    }
+pub fn find_number_of_pairs_overlapping(input: Input) -> Result<u32, Error> {
+    Ok(count_pairs_overlapping(&first_part::read_pairs(input)?))
 }
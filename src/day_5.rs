@@ -1,2 +1,33 @@
+mod drawing;
 mod first_part;
 mod second_part;
+
+use crate::{Error, Input, Parsed, Part, Solver};
+
+use first_part::Procedure;
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::run_day_five_first_part(input)
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::run_day_five_second_part(input)
+    }
+
+    fn parse(&self, input: Input) -> Result<Parsed, Error> {
+        Ok(Box::new(first_part::read_procedure(input)?))
+    }
+
+    fn solve_parsed(&self, part: Part, parsed: Parsed) -> Result<String, Error> {
+        let procedure = *parsed
+            .downcast::<Procedure>()
+            .expect("parsed by Day5::parse");
+        match part {
+            Part::One => first_part::move_crates_one_at_a_time(procedure),
+            Part::Two => second_part::move_crates_at_once(procedure),
+        }
+    }
+}
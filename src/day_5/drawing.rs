@@ -0,0 +1,161 @@
+//! The drawing of the starting stacks at the top of the input, like
+//!
+//! ```text
+//!     [D]
+//! [N] [C]
+//! [Z] [M] [P]
+//!  1   2   3
+//! ```
+//!
+//! followed by a blank line and the moves.
+
+use std::iter::Peekable;
+
+use crate::{Error, ParseError};
+
+use super::first_part::my_dock;
+
+/// Reads the stacks drawn at the top of `lines`, bottom crate first, leaving
+/// the moves after the blank line below them. Inputs that start right with the
+/// moves, like mine, get [`my_dock`].
+pub(super) fn read_dock(
+    lines: &mut Peekable<impl Iterator<Item = (usize, Result<String, Error>)>>,
+) -> Result<Vec<Vec<char>>, Error> {
+    let starts_with_drawing = match lines.peek() {
+        Some((_, Ok(line))) => !line.is_empty() && !line.starts_with("move"),
+        _ => false,
+    };
+    if !starts_with_drawing {
+        return Ok(my_dock());
+    }
+
+    let mut rows = Vec::new();
+    for (line_index, line) in lines.by_ref() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        rows.push((line_index + 1, line));
+    }
+    let (line, numbers) = rows.pop().expect("the drawing has its first line");
+    let stacks = count_stacks(&numbers).map_err(|error| error.on_line(line))?;
+
+    let mut dock = vec![Vec::new(); stacks];
+    // a stack with an empty slot has no crates above it
+    let mut topped = vec![false; stacks];
+    for (line, row) in rows.iter().rev() {
+        let row: Vec<char> = row.chars().collect();
+        for (stack, crates) in dock.iter_mut().enumerate() {
+            let slot: String = row.iter().skip(4 * stack).take(3).collect();
+            match slot.as_bytes() {
+                [b'[', label, b']'] if label.is_ascii_uppercase() && !topped[stack] => {
+                    crates.push(char::from(*label))
+                }
+                _ if slot.trim().is_empty() => topped[stack] = true,
+                _ => {
+                    let expected = if topped[stack] {
+                        "blanks, as the stack ends below"
+                    } else {
+                        "a crate like `[A]` or blanks"
+                    };
+                    return Err(ParseError::new(&slot, expected)
+                        .shifted(4 * stack)
+                        .on_line(*line)
+                        .into());
+                }
+            }
+        }
+        let rest: String = row.iter().skip(4 * stacks).collect();
+        if !rest.trim().is_empty() {
+            let offset = 4 * stacks + rest.len() - rest.trim_start().len();
+            return Err(ParseError::new(rest.trim(), "end of the row")
+                .shifted(offset)
+                .on_line(*line)
+                .into());
+        }
+    }
+    Ok(dock)
+}
+
+/// Counts the stacks on the row numbering them, which goes `1`, `2` and so on.
+fn count_stacks(numbers: &str) -> Result<usize, ParseError> {
+    let mut stacks = 0;
+    let mut offset = 0;
+    for word in numbers.split(' ') {
+        if !word.is_empty() {
+            stacks += 1;
+            if word != stacks.to_string() {
+                return Err(ParseError::new(word, format!("stack number {stacks}")).shifted(offset));
+            }
+        }
+        offset += word.len() + 1;
+    }
+    if stacks == 0 {
+        return Err(ParseError::new(numbers, "stack number 1"));
+    }
+    Ok(stacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Input;
+
+    use super::*;
+
+    fn read(text: &str) -> Result<Vec<Vec<char>>, Error> {
+        read_dock(&mut Input::from_text(text).lines().enumerate().peekable())
+    }
+
+    #[test]
+    fn should_read_stacks_bottom_crate_first() {
+        let input = Input::from_path("./inputs/day_5/mocks/example.txt").unwrap();
+        let mut lines = input.lines().enumerate().peekable();
+        assert_eq!(
+            read_dock(&mut lines).unwrap(),
+            vec![vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        let (line_index, line) = lines.next().unwrap();
+        assert_eq!(
+            (line_index, line.unwrap()),
+            (5, "move 1 from 2 to 1".to_string())
+        );
+    }
+
+    #[test]
+    fn should_fall_back_to_my_dock_without_drawing() {
+        assert_eq!(read("move 1 from 2 to 1\n").unwrap(), my_dock());
+    }
+
+    #[test]
+    fn should_point_at_malformed_crate() {
+        match read("[A] (B)\n 1   2\n\n") {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (1, 5));
+                assert_eq!(error.token, "(B)");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_reject_crate_floating_over_empty_slot() {
+        match read("    [A]\n[B]    \n 1   2\n\n") {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (1, 5));
+                assert_eq!(error.expected, "blanks, as the stack ends below");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn should_point_at_misnumbered_stack() {
+        match read("[A] [B]\n 1   3\n\n") {
+            Err(Error::Parse(error)) => {
+                assert_eq!((error.line, error.column), (2, 6));
+                assert_eq!(error.expected, "stack number 2");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+}
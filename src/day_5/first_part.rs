@@ -1,28 +1,66 @@
-use std::{io::BufRead, str::FromStr};
+use std::str::FromStr;
 
-use crate::read_file;
+use crate::{info, trace, Error, Input, ParseError};
+
+use super::drawing::read_dock;
 
 pub struct Crane;
 
 impl Crane {
-    fn perform_operation(operation: &Operation, dock: &mut Vec<Vec<char>>) {
+    fn perform_operation(operation: &Operation, dock: &mut [Vec<char>]) -> Result<(), String> {
+        trace!("Operation: {:?}", operation);
+        trace!("Dock {:?}", dock);
+        let (source, destination) = Crane::stacks_of(operation, dock)?;
         for _move_repetition in 0..operation.move_repetition {
-            println!("Operation: {:?}", operation);
-            println!("Dock {:?}", dock);
-            let source = usize::try_from(operation.move_from_stack - 1).unwrap();
-            let destination = usize::try_from(operation.destination_stack - 1).unwrap();
-            let stack = dock.get_mut(source).unwrap();
-            let movable_crate = stack.pop().unwrap();
-            let destination_stack = dock.get_mut(destination).unwrap();
-            destination_stack.push(movable_crate);
+            let movable_crate = dock[source]
+                .pop()
+                .expect("checked the stack holds enough crates");
+            dock[destination].push(movable_crate);
+        }
+        Ok(())
+    }
+
+    /// Indexes of the stacks `operation` moves crates between, once it is
+    /// known both are on the dock and the first holds enough crates.
+    pub(super) fn stacks_of(
+        operation: &Operation,
+        dock: &[Vec<char>],
+    ) -> Result<(usize, usize), String> {
+        let index_of = |stack: u8| {
+            let index = usize::from(stack) - 1;
+            if index < dock.len() {
+                Ok(index)
+            } else {
+                Err(format!(
+                    "stack {stack} is not on the dock, which has {} stacks",
+                    dock.len()
+                ))
+            }
+        };
+        let source = index_of(operation.move_from_stack)?;
+        let destination = index_of(operation.destination_stack)?;
+        let crates = dock[source].len();
+        if crates < usize::from(operation.move_repetition) {
+            return Err(format!(
+                "cannot move {} crates from stack {}, which holds {crates}",
+                operation.move_repetition, operation.move_from_stack
+            ));
+        }
+        Ok((source, destination))
+    }
+
+    /// Error for the operation on line `line` the crane could not perform.
+    pub(super) fn failed_on_line(message: String, line: usize) -> Error {
+        Error::Unsolvable {
+            message: format!("line {line}: {message}"),
         }
     }
 
     pub fn get_top_crates(dock: Vec<Vec<char>>) -> String {
         let mut top_crates = "".to_string();
-        for stack in dock {
-            let top_crate_of_stack = stack.last().unwrap().to_owned();
-            top_crates.push(top_crate_of_stack);
+        // a stack emptied by the moves has no crate on top to read
+        for top_crate_of_stack in dock.iter().filter_map(|stack| stack.last()) {
+            top_crates.push(*top_crate_of_stack);
         }
         top_crates
     }
@@ -36,60 +74,172 @@ pub struct Operation {
 }
 
 impl FromStr for Operation {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(" from ").collect();
-        debug_assert!(split.len() == 2);
-        let move_repetition: Vec<&str> = split[0].split("move ").collect();
-        debug_assert!(move_repetition.len() == 2);
-        let move_operation: Vec<&str> = split[1].split(" to ").collect();
-        debug_assert!(move_operation.len() == 2);
+        // `None` marks where the numbers go in `move 1 from 2 to 3`.
+        const SHAPE: [Option<&str>; 6] = [Some("move"), None, Some("from"), None, Some("to"), None];
+
+        let mut words = s.split(' ');
+        let mut numbers: Vec<u8> = Vec::with_capacity(3);
+        let mut offset = 0;
+        for expected_word in SHAPE {
+            let word = words.next().unwrap_or_default();
+            match expected_word {
+                Some(keyword) if word != keyword => {
+                    return Err(ParseError::new(word, format!("`{keyword}`")).shifted(offset))
+                }
+                Some(_) => {}
+                // the count of crates comes first, stacks are numbered from 1
+                None if numbers.is_empty() => numbers.push(u8::from_str(word).map_err(|_| {
+                    ParseError::new(word, "a number from 0 to 255").shifted(offset)
+                })?),
+                None => numbers.push(
+                    u8::from_str(word)
+                        .ok()
+                        .filter(|&stack| stack > 0)
+                        .ok_or_else(|| {
+                            ParseError::new(word, "a stack number from 1 to 255").shifted(offset)
+                        })?,
+                ),
+            }
+            offset += word.len() + 1;
+        }
+        if let Some(word) = words.next() {
+            return Err(ParseError::new(word, "end of the instruction").shifted(offset));
+        }
+
         Ok(Operation {
-            move_repetition: u8::from_str(move_repetition[1]).unwrap(),
-            move_from_stack: u8::from_str(move_operation[0]).unwrap(),
-            destination_stack: u8::from_str(move_operation[1]).unwrap(),
+            move_repetition: numbers[0],
+            move_from_stack: numbers[1],
+            destination_stack: numbers[2],
         })
     }
 }
 
-#[allow(dead_code)]
-fn run_day_five_first_part(filename: &str, dock: &mut Vec<Vec<char>>) -> String {
-    let reader = read_file(filename);
+/// Starting stacks of my puzzle input, bottom crate first. My input file only
+/// holds the move instructions, so its drawing is added by hand.
+pub fn my_dock() -> Vec<Vec<char>> {
+    vec![
+        vec!['S', 'C', 'V', 'N'],
+        vec!['Z', 'M', 'J', 'H', 'N', 'S'],
+        vec!['M', 'C', 'T', 'G', 'J', 'N', 'D'],
+        vec!['T', 'D', 'F', 'J', 'W', 'R', 'M'],
+        vec!['P', 'F', 'H'],
+        vec!['C', 'T', 'Z', 'H', 'J'],
+        vec!['D', 'P', 'R', 'Q', 'F', 'S', 'L', 'Z'],
+        vec!['C', 'S', 'L', 'H', 'D', 'F', 'P', 'W'],
+        vec!['D', 'S', 'M', 'P', 'F', 'N', 'G', 'Z'],
+    ]
+}
 
-    for line in reader.lines() {
-        let instruction = line.unwrap();
-        let operation = Operation::from_str(&instruction).unwrap();
-        Crane::perform_operation(&operation, dock);
+/// The starting stacks and the moves to rearrange them with, each with the
+/// line it is on.
+pub struct Procedure {
+    pub dock: Vec<Vec<char>>,
+    pub operations: Vec<(usize, Operation)>,
+}
+
+/// Reads the stacks drawn at the top of `input` and the moves below them.
+pub fn read_procedure(input: Input) -> Result<Procedure, Error> {
+    let mut lines = input.lines().enumerate().peekable();
+    let dock = read_dock(&mut lines)?;
+    let mut operations = Vec::new();
+    for (line_index, line) in lines {
+        let instruction = line?;
+        let operation =
+            Operation::from_str(&instruction).map_err(|error| error.on_line(line_index + 1))?;
+        operations.push((line_index + 1, operation));
     }
+    Ok(Procedure { dock, operations })
+}
+
+/// Performs every move of `procedure` with `perform`, and reads the crates on
+/// top afterwards.
+pub(super) fn rearrange(
+    procedure: Procedure,
+    perform: fn(&Operation, &mut [Vec<char>]) -> Result<(), String>,
+) -> Result<String, Error> {
+    let Procedure {
+        mut dock,
+        operations,
+    } = procedure;
+    for (line, operation) in &operations {
+        perform(operation, &mut dock).map_err(|message| Crane::failed_on_line(message, *line))?;
+    }
+
+    info!("Final Stacks: {:?}", dock);
+
+    Ok(Crane::get_top_crates(dock))
+}
 
-    println!("Final Stacks: {:?}", dock);
+/// Moves the crates one at a time, like the CrateMover 9000.
+pub fn move_crates_one_at_a_time(procedure: Procedure) -> Result<String, Error> {
+    rearrange(procedure, Crane::perform_operation)
+}
 
-    Crane::get_top_crates(dock.to_vec())
-    // "LOL".to_string()
+pub fn run_day_five_first_part(input: Input) -> Result<String, Error> {
+    move_crates_one_at_a_time(read_procedure(input)?)
 }
+
 #[cfg(test)]
 mod tests {
-    use super::run_day_five_first_part;
+    use std::str::FromStr;
+
+    use crate::Input;
+
+    use super::{run_day_five_first_part, Operation};
+
+    #[test]
+    fn should_parse_operation() {
+        let operation = Operation::from_str("move 12 from 2 to 9").unwrap();
+        assert_eq!(operation.move_repetition, 12);
+        assert_eq!(operation.move_from_stack, 2);
+        assert_eq!(operation.destination_stack, 9);
+    }
+
+    #[test]
+    fn should_point_at_malformed_part_of_operation() {
+        let error = Operation::from_str("move 1 form 2 to 3").unwrap_err();
+        assert_eq!(error.column, 8);
+        assert_eq!(error.token, "form");
+
+        let error = Operation::from_str("move 1 from 2 to").unwrap_err();
+        assert_eq!(error.column, 18);
+        assert_eq!(error.token, "");
+    }
+
+    #[test]
+    fn should_reject_stack_zero() {
+        let error = Operation::from_str("move 1 from 0 to 1").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (13, "0"));
+        assert_eq!(error.expected, "a stack number from 1 to 255");
+        assert!(Operation::from_str("move 0 from 1 to 2").is_ok());
+    }
+
+    #[test]
+    fn should_report_operation_the_crane_cannot_perform() {
+        let run = |instructions: &str| {
+            let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+            run_day_five_first_part(Input::from_text(&format!("{drawing}{instructions}")))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            run("move 1 from 1 to 2\nmove 1 from 1 to 12\n"),
+            "line 7: stack 12 is not on the dock, which has 3 stacks"
+        );
+        assert_eq!(
+            run("move 1 from 3 to 1\nmove 1 from 3 to 1\n"),
+            "line 7: cannot move 1 crates from stack 3, which holds 0"
+        );
+    }
 
     #[test]
     fn test_example() {
-        let file_path = "./inputs/day_5/aoc_input.txt";
-        // I didn't want to write a parser for this, so added by hand.
-        let mut dock: Vec<Vec<char>> = vec![
-            vec!['S', 'C', 'V', 'N'],
-            vec!['Z', 'M', 'J', 'H', 'N', 'S'],
-            vec!['M', 'C', 'T', 'G', 'J', 'N', 'D'],
-            vec!['T', 'D', 'F', 'J', 'W', 'R', 'M'],
-            vec!['P', 'F', 'H'],
-            vec!['C', 'T', 'Z', 'H', 'J'],
-            vec!['D', 'P', 'R', 'Q', 'F', 'S', 'L', 'Z'],
-            vec!['C', 'S', 'L', 'H', 'D', 'F', 'P', 'W'],
-            vec!['D', 'S', 'M', 'P', 'F', 'N', 'G', 'Z'],
-        ];
-        debug_assert!(dock.len() == 9);
-
-        let result = run_day_five_first_part(file_path, dock.as_mut());
-        assert_eq!(result, "???");
+        let file_path = "./inputs/day_5/mocks/example.txt";
+
+        let result = run_day_five_first_part(Input::from_path(file_path).unwrap()).unwrap();
+        assert_eq!(result, "CMZ");
     }
 }
@@ -1,59 +1,50 @@
-use std::{io::BufRead, str::FromStr};
+use crate::{day_5::first_part::Operation, Error, Input};
 
-use crate::{day_5::first_part::Operation, read_file};
-
-use super::first_part::Crane;
+use super::first_part::{self, Crane, Procedure};
 
 impl Crane {
-    fn perform_operation_9001_model(operation: &Operation, dock: &mut [Vec<char>]) {
-        let source = usize::try_from(operation.move_from_stack - 1).unwrap();
-        let destination = usize::try_from(operation.destination_stack - 1).unwrap();
-        let move_repetition = usize::try_from(operation.move_repetition).unwrap();
-        let stack = dock.get_mut(source).unwrap();
+    fn perform_operation_9001_model(
+        operation: &Operation,
+        dock: &mut [Vec<char>],
+    ) -> Result<(), String> {
+        let (source, destination) = Crane::stacks_of(operation, dock)?;
+        let move_repetition = usize::from(operation.move_repetition);
+        let stack = &mut dock[source];
         let movable_crates: Vec<char> = stack.drain(stack.len() - move_repetition..).collect();
-        let destination_stack = dock.get_mut(destination).unwrap();
-        for i in movable_crates {
-            destination_stack.push(i);
-        }
+        dock[destination].extend(movable_crates);
+        Ok(())
     }
 }
 
-#[allow(dead_code)]
-fn run_day_five_second_part(filename: &str, dock: &mut Vec<Vec<char>>) -> String {
-    let reader = read_file(filename);
-
-    for line in reader.lines() {
-        let instruction = line.unwrap();
-        let operation = Operation::from_str(&instruction).unwrap();
-        Crane::perform_operation_9001_model(&operation, dock);
-    }
-
-    println!("Final Stacks: {:?}", dock);
+/// Moves the crates of a move all at once, like the CrateMover 9001.
+pub fn move_crates_at_once(procedure: Procedure) -> Result<String, Error> {
+    first_part::rearrange(procedure, Crane::perform_operation_9001_model)
+}
 
-    Crane::get_top_crates(dock.to_vec())
+pub fn run_day_five_second_part(input: Input) -> Result<String, Error> {
+    move_crates_at_once(first_part::read_procedure(input)?)
 }
 #[cfg(test)]
 mod tests {
+    use crate::Input;
+
     use super::run_day_five_second_part;
 
     #[test]
     fn test_example() {
-        let file_path = "./inputs/day_5/aoc_input.txt";
-        // I didn't want to write a parser for this, so added by hand.
-        let mut dock: Vec<Vec<char>> = vec![
-            vec!['S', 'C', 'V', 'N'],
-            vec!['Z', 'M', 'J', 'H', 'N', 'S'],
-            vec!['M', 'C', 'T', 'G', 'J', 'N', 'D'],
-            vec!['T', 'D', 'F', 'J', 'W', 'R', 'M'],
-            vec!['P', 'F', 'H'],
-            vec!['C', 'T', 'Z', 'H', 'J'],
-            vec!['D', 'P', 'R', 'Q', 'F', 'S', 'L', 'Z'],
-            vec!['C', 'S', 'L', 'H', 'D', 'F', 'P', 'W'],
-            vec!['D', 'S', 'M', 'P', 'F', 'N', 'G', 'Z'],
-        ];
-        debug_assert!(dock.len() == 9);
-
-        let result = run_day_five_second_part(file_path, dock.as_mut());
-        assert_eq!(result, "???");
+        let file_path = "./inputs/day_5/mocks/example.txt";
+
+        let result = run_day_five_second_part(Input::from_path(file_path).unwrap()).unwrap();
+        assert_eq!(result, "MCD");
+    }
+
+    #[test]
+    fn should_report_moving_more_crates_than_the_stack_holds() {
+        let input = Input::from_text("[N]    \n[Z] [M]\n 1   2 \n\nmove 3 from 1 to 2\n");
+        let error = run_day_five_second_part(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5: cannot move 3 crates from stack 1, which holds 2"
+        );
     }
 }
@@ -1,2 +1,16 @@
 mod first_part;
 mod second_part;
+
+use crate::{Error, Input, Solver};
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    fn part_one(&self, input: Input) -> Result<String, Error> {
+        first_part::run_day_six_part_one(input).map(|answer| answer.to_string())
+    }
+
+    fn part_two(&self, input: Input) -> Result<String, Error> {
+        second_part::run_day_six_part_two(input).map(|answer| answer.to_string())
+    }
+}
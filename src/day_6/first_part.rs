@@ -1,17 +1,14 @@
-use std::{collections::HashSet, io::BufRead};
+use std::collections::HashSet;
 
-use crate::read_file;
-
-#[allow(dead_code)]
-fn run_day_six_part_one(filename: &str) -> u32 {
-    let reader = read_file(filename);
+use crate::{debug, Error, Input};
 
+pub fn run_day_six_part_one(input: Input) -> Result<u32, Error> {
     let mut result = 0;
-    for line in reader.lines() {
-        let data_stream = line.unwrap();
+    for line in input.lines() {
+        let data_stream = line?;
         result = parse_signal_start_location(data_stream);
     }
-    result
+    Ok(result)
 }
 
 fn parse_signal_start_location(data_stream: String) -> u32 {
@@ -26,7 +23,7 @@ fn parse_signal_start_location(data_stream: String) -> u32 {
             match has_duplicates(potential_start_of_packet) {
                 true => continue,
                 false => {
-                    println!("THIS IS IT: {:?}", potential_start_of_packet);
+                    debug!("THIS IS IT: {:?}", potential_start_of_packet);
                     break;
                 }
             }
@@ -48,51 +45,3 @@ pub fn has_duplicates(chars: &[char]) -> bool {
     }
     has_duplicate
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run_day_six_part_one;
-
-    #[test]
-    fn test_example_one() {
-        let filename = "./inputs/day_6/mocks/example_1.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 7);
-    }
-
-    #[test]
-    fn test_example_two() {
-        let filename = "./inputs/day_6/mocks/example_2.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 5);
-    }
-
-    #[test]
-    fn test_example_three() {
-        let filename = "./inputs/day_6/mocks/example_3.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 6);
-    }
-
-    #[test]
-    fn test_example_four() {
-        let filename = "./inputs/day_6/mocks/example_4.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 10);
-    }
-
-    #[test]
-    fn test_example_five() {
-        let filename = "./inputs/day_6/mocks/example_5.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 11);
-    }
-
-    #[test]
-    #[ignore]
-    fn actual_test() {
-        let filename = "./inputs/day_6/aoc_input.txt";
-        let result = run_day_six_part_one(filename);
-        assert_eq!(result, 0);
-    }
-}
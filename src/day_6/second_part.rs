@@ -1,19 +1,14 @@
-use std::io::BufRead;
-
-use crate::read_file;
+use crate::{debug, Error, Input};
 
 use super::first_part::has_duplicates;
 
-#[allow(dead_code)]
-fn run_day_six_part_two(filename: &str) -> u32 {
-    let reader = read_file(filename);
-
+pub fn run_day_six_part_two(input: Input) -> Result<u32, Error> {
     let mut result = 0;
-    for line in reader.lines() {
-        let data_stream = line.unwrap();
+    for line in input.lines() {
+        let data_stream = line?;
         result = parse_start_of_message(data_stream);
     }
-    result
+    Ok(result)
 }
 
 fn parse_start_of_message(data_stream: String) -> u32 {
@@ -28,7 +23,7 @@ fn parse_start_of_message(data_stream: String) -> u32 {
             match has_duplicates(potential_start_of_packet) {
                 true => continue,
                 false => {
-                    println!("THIS IS IT: {:?}", potential_start_of_packet);
+                    debug!("THIS IS IT: {:?}", potential_start_of_packet);
                     break;
                 }
             }
@@ -36,51 +31,3 @@ fn parse_start_of_message(data_stream: String) -> u32 {
     }
     u32::try_from(buf.len()).unwrap()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::run_day_six_part_two;
-
-    #[test]
-    fn test_example_one() {
-        let filename = "./inputs/day_6/mocks/example_1.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 19);
-    }
-
-    #[test]
-    fn test_example_two() {
-        let filename = "./inputs/day_6/mocks/example_2.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 23);
-    }
-
-    #[test]
-    fn test_example_three() {
-        let filename = "./inputs/day_6/mocks/example_3.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 23);
-    }
-
-    #[test]
-    fn test_example_four() {
-        let filename = "./inputs/day_6/mocks/example_4.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 29);
-    }
-
-    #[test]
-    fn test_example_five() {
-        let filename = "./inputs/day_6/mocks/example_5.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 26);
-    }
-
-    #[test]
-    #[ignore]
-    fn actual_test() {
-        let filename = "./inputs/day_6/aoc_input.txt";
-        let result = run_day_six_part_two(filename);
-        assert_eq!(result, 0);
-    }
-}
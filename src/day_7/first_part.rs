@@ -1,12 +1,6 @@
-use std::{
-    collections::HashMap,
-    fs::File as FsFile,
-    io::{BufRead, BufReader, Lines},
-    iter::Peekable,
-    str::FromStr,
-};
+use std::{collections::HashMap, iter::Peekable, str::FromStr};
 
-use crate::read_file;
+use crate::{debug, info, input::Lines, trace, Error, Input, ParseError};
 
 #[derive(Debug, PartialEq, Eq)]
 enum DirectoryContent {
@@ -46,6 +40,7 @@ impl File {
     }
 }
 
+#[allow(dead_code)]
 #[derive(Debug, PartialEq, Eq)]
 enum ChangeDirectoryArg {
     Back,
@@ -55,7 +50,7 @@ enum ChangeDirectoryArg {
 impl FromStr for ChangeDirectoryArg {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if let false = s.is_empty() {
+        if !s.is_empty() {
             return Err("No arg present for command".to_string());
         }
 
@@ -97,19 +92,18 @@ enum ListCommandOutput {
 }
 
 impl FromStr for ListCommandOutput {
-    type Err = ();
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split: Vec<&str> = s.split(' ').collect();
-        debug_assert!(split.len() == 2);
-
-        let first_text = split.first().unwrap().to_owned();
-        let name = split.last().unwrap().to_owned();
+        let (first_text, name) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(s, "`dir <name>` or `<size> <name>`"))?;
 
         match first_text {
             "dir" => Ok(ListCommandOutput::Directory(name.to_string())),
             _ => Ok(ListCommandOutput::File(
                 name.to_string(),
-                u32::from_str(first_text).unwrap(),
+                u32::from_str(first_text)
+                    .map_err(|_| ParseError::new(first_text, "`dir` or a file size"))?,
             )),
         }
     }
@@ -133,9 +127,11 @@ impl FromStr for Command {
     }
 }
 
-fn handle_ls_output(line_data: String) -> (String, DirectoryContent) {
-    let ls_command_output_type = ListCommandOutput::from_str(&line_data).unwrap();
-    match ls_command_output_type {
+/// Reads the `ls` output on line `line`.
+fn handle_ls_output(line_data: String, line: usize) -> Result<(String, DirectoryContent), Error> {
+    let ls_command_output_type =
+        ListCommandOutput::from_str(&line_data).map_err(|error| error.on_line(line))?;
+    Ok(match ls_command_output_type {
         ListCommandOutput::Directory(name) => {
             let directory = Directory::new(name.clone());
             (name, DirectoryContent::Directory(directory))
@@ -144,7 +140,7 @@ fn handle_ls_output(line_data: String) -> (String, DirectoryContent) {
             let file = File::new(name.clone(), size);
             (name, DirectoryContent::File(file))
         }
-    }
+    })
 }
 
 /// This will take in an iter, and we will assume the first item will be the
@@ -155,17 +151,26 @@ fn handle_ls_output(line_data: String) -> (String, DirectoryContent) {
 /// call itself, and pass along the iter recursively.
 ///
 /// The recursive iteration will then handle the nested directory.
-fn recursive_file_size_reader(line_iter: &mut Peekable<Lines<BufReader<FsFile>>>) -> Directory {
+///
+/// `lines_read` counts the lines taken from the iter so far, across the
+/// recursion, so errors can say which line they are on.
+fn recursive_file_size_reader(
+    line_iter: &mut Peekable<Lines>,
+    lines_read: &mut usize,
+) -> Result<Directory, Error> {
+    *lines_read += 1;
     let first_command = Command::from_str(&line_iter.next().unwrap().unwrap()).unwrap();
-    println!("First command (cd): {first_command:?}");
+    debug!("First command (cd): {first_command:?}");
     let mut directory = Directory::new(Command::get_directory_name(first_command).unwrap());
 
+    *lines_read += 1;
     let second_command = Command::from_str(&line_iter.next().unwrap().unwrap()).unwrap();
-    println!("Second command (ls): {second_command:?}");
+    debug!("Second command (ls): {second_command:?}");
 
     while Command::from_str(line_iter.peek().unwrap().as_ref().unwrap()).is_err() {
         let ls_output = line_iter.next().unwrap().unwrap();
-        let (name, content) = handle_ls_output(ls_output);
+        *lines_read += 1;
+        let (name, content) = handle_ls_output(ls_output, *lines_read)?;
         directory.add_content(name, content);
     }
 
@@ -174,10 +179,10 @@ fn recursive_file_size_reader(line_iter: &mut Peekable<Lines<BufReader<FsFile>>>
     let next_command_peek = line_iter.peek().unwrap().as_ref();
     if next_command_peek.is_err() {
         // return early
-        return directory;
+        return Ok(directory);
     }
     let next_cd_command = Command::from_str(line_iter.peek().unwrap().as_ref().unwrap()).unwrap();
-    println!("Next command (peek): {next_cd_command:?}");
+    debug!("Next command (peek): {next_cd_command:?}");
 
     match next_cd_command {
         Command::ListContents => panic!(),
@@ -185,14 +190,15 @@ fn recursive_file_size_reader(line_iter: &mut Peekable<Lines<BufReader<FsFile>>>
             if direction == ".." {
                 // return from this recursive fn
                 line_iter.next();
-                return directory;
+                *lines_read += 1;
+                return Ok(directory);
             } else {
                 // start another recursion
-                let recursive_directory = recursive_file_size_reader(line_iter);
+                let recursive_directory = recursive_file_size_reader(line_iter, lines_read)?;
                 // line_iter.next();
-                println!("Returned from {:?}", recursive_directory.name);
+                debug!("Returned from {:?}", recursive_directory.name);
                 let peep = line_iter.peek().unwrap().as_ref().unwrap();
-                println!("Next command peek: {:?}", peep);
+                trace!("Next command peek: {:?}", peep);
                 let recursive_directory_name = recursive_directory.name.clone();
                 directory.contents.insert(
                     recursive_directory_name,
@@ -202,29 +208,38 @@ fn recursive_file_size_reader(line_iter: &mut Peekable<Lines<BufReader<FsFile>>>
         }
     };
 
-    directory
+    Ok(directory)
 }
 
 #[allow(dead_code)]
-fn run_day_seven_part_one(filename: &str) -> u32 {
-    let reader = read_file(filename);
-    let mut line_iter = reader.lines().peekable();
+fn run_day_seven_part_one(input: Input) -> Result<u32, Error> {
+    let mut line_iter = input.lines().peekable();
 
-    let result = recursive_file_size_reader(&mut line_iter);
-    println!("Result");
-    println!("{:?}", result);
-    0
+    let result = recursive_file_size_reader(&mut line_iter, &mut 0)?;
+    info!("Result: {:?}", result);
+    Ok(0)
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::Input;
 
     use super::run_day_seven_part_one;
 
     #[test]
     fn test_provided_example() {
         let file_path = "./inputs/day_7/mocks/example.txt";
-        let result = run_day_seven_part_one(file_path);
+        let result = run_day_seven_part_one(Input::from_path(file_path).unwrap()).unwrap();
         assert_eq!(result, 95437);
     }
+
+    #[test]
+    fn should_report_malformed_ls_output_with_its_line() {
+        let input = Input::from_text("$ cd /\n$ ls\n14848514 b.txt\nabc d.txt\n");
+        let error = run_day_seven_part_one(input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected `dir` or a file size, found \"abc\""
+        );
+    }
 }
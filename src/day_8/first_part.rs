@@ -1,17 +1,14 @@
-use std::io::BufRead;
+use crate::Input;
 
-use crate::read_file;
-
-fn run_day_eight_part_one(filename: &str) {
-    let reader = read_file(filename);
-
-    for line in reader.lines() {}
+#[allow(dead_code)]
+fn run_day_eight_part_one(input: Input) {
+    for _line in input.lines() {}
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
     fn test_example() {
-        let filename = "./inputs/day_8/mocks/example.txt";
+        let _filename = "./inputs/day_8/mocks/example.txt";
     }
 }
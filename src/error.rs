@@ -0,0 +1,153 @@
+use std::{error, fmt, io};
+
+use crate::input::Origin;
+
+/// Everything that can go wrong while loading an input or solving a puzzle.
+#[derive(Debug)]
+pub enum Error {
+    /// Opening, reading or writing a file failed.
+    Io { origin: Origin, source: io::Error },
+    /// A line of the input did not have the shape the puzzle describes.
+    Parse(ParseError),
+    /// A file the tooling keeps next to the inputs, like saved benchmark
+    /// results, could not be understood.
+    InvalidFile { origin: Origin, message: String },
+    /// No solution is registered for the requested day.
+    UnknownDay(u8),
+    /// The day has no mode by this name, see [`Solver::modes`](crate::Solver::modes).
+    UnknownMode {
+        mode: String,
+        available: Vec<&'static str>,
+    },
+    /// An option given to a mode could not be understood.
+    InvalidOption { name: String, message: String },
+    /// A running total grew past what its type can hold. `what` says which
+    /// total, and where in the input it was.
+    Overflow { what: String },
+    /// The input has the shape the puzzle describes, but not the answer it
+    /// promises, like a group of elves with no badge in common.
+    Unsolvable { message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { origin, source } => write!(f, "{origin}: {source}"),
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::InvalidFile { origin, message } => write!(f, "{origin} is invalid: {message}"),
+            Error::UnknownDay(day) => write!(f, "day {day} has no registered solution"),
+            Error::UnknownMode { mode, available } if available.is_empty() => {
+                write!(f, "unknown mode {mode:?}, this day has no modes")
+            }
+            Error::UnknownMode { mode, available } => write!(
+                f,
+                "unknown mode {mode:?}, expected one of {}",
+                available.join(", ")
+            ),
+            Error::InvalidOption { name, message } => write!(f, "option {name}: {message}"),
+            Error::Overflow { what } => write!(f, "{what} overflowed"),
+            Error::Unsolvable { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(error) => Some(error),
+            Error::InvalidFile { .. }
+            | Error::UnknownDay(_)
+            | Error::UnknownMode { .. }
+            | Error::InvalidOption { .. }
+            | Error::Overflow { .. }
+            | Error::Unsolvable { .. } => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+/// Points at the token of a line that could not be parsed, and says what was
+/// expected in its place.
+///
+/// `FromStr` implementations only see the piece of the line they parse, so they
+/// report columns relative to that piece and leave `line` at `0`. The caller
+/// that owns the whole line fills the rest in with [`ParseError::shifted`] and
+/// [`ParseError::on_line`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number, `0` while unknown.
+    pub line: usize,
+    /// 1-based column the offending token starts at.
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(token: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line: 0,
+            column: 1,
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Moves the column right by `offset` characters, for when the parsed
+    /// piece did not start at the beginning of the line.
+    pub fn shifted(mut self, offset: usize) -> Self {
+        self.column += offset;
+        self
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // parsed pieces that are not lines of an input, like options, have
+        // no line to point at
+        if self.line > 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        write!(
+            f,
+            "column {}: expected {}, found {:?}",
+            self.column, self.expected, self.token
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_point_at_token_within_the_whole_line() {
+        let error = ParseError::new("Q", "one of X, Y, Z").shifted(2).on_line(7);
+        assert_eq!(
+            Error::from(error).to_string(),
+            "line 7, column 3: expected one of X, Y, Z, found \"Q\""
+        );
+    }
+
+    #[test]
+    fn should_leave_out_unknown_line() {
+        let error = ParseError::new("Q", "one of X, Y, Z");
+        assert_eq!(
+            error.to_string(),
+            "column 1: expected one of X, Y, Z, found \"Q\""
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Expected answers of the examples under `inputs/day_N/mocks/`.
+//!
+//! An example can carry its expected answers in a sidecar file next to it,
+//! named like the example but ending in `.toml`, so `mocks/first_part/win.txt`
+//! is checked against `mocks/first_part/win.toml`:
+//!
+//! ```toml
+//! part_1 = 24
+//! part_2 = "15"
+//! ```
+//!
+//! A part without an expected answer is not checked, and examples without a
+//! sidecar are left alone, e.g. when they need more than the input to solve.
+
+use std::{collections::BTreeMap, fs};
+
+use crate::{
+    answers::answer_from_value,
+    input::Origin,
+    input_cache::{InputCache, InputKind},
+    Error, Part, Solver,
+};
+
+/// What an example is expected to solve to.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Expected {
+    inner: BTreeMap<Part, String>,
+}
+
+impl Expected {
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let parts: BTreeMap<String, toml::Value> =
+            toml::from_str(text).map_err(|error| error.to_string())?;
+
+        let mut expected = Expected::default();
+        for (key, value) in parts {
+            let part = match key.as_str() {
+                "part_1" => Part::One,
+                "part_2" => Part::Two,
+                _ => return Err(format!("expected part_1 or part_2, found {key}")),
+            };
+            let answer =
+                answer_from_value(value).map_err(|message| format!("{message} in {key}"))?;
+            expected.inner.insert(part, answer);
+        }
+        Ok(expected)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.inner.get(&part).map(String::as_str)
+    }
+}
+
+impl InputCache {
+    /// Expected answers stored next to the example `name` of `day`, `None`
+    /// when it has no sidecar.
+    pub fn expected(&self, day: u8, name: &str) -> Result<Option<Expected>, Error> {
+        let path = self
+            .path(day, &InputKind::Example(name.to_string()))
+            .with_extension("toml");
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let origin = Origin::Path(path.clone());
+        let text = fs::read_to_string(&path).map_err(|source| Error::Io {
+            origin: origin.clone(),
+            source,
+        })?;
+        Expected::from_toml(&text)
+            .map(Some)
+            .map_err(|message| Error::InvalidFile { origin, message })
+    }
+}
+
+/// Result of solving one example that has an expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleCheck {
+    pub name: String,
+    pub expected: String,
+    /// The answer, or the error the solver failed with.
+    pub actual: Result<String, String>,
+}
+
+impl ExampleCheck {
+    pub fn passed(&self) -> bool {
+        self.actual.as_deref() == Ok(self.expected.as_str())
+    }
+}
+
+/// Solves `part` of every example of `day` that has an expected answer for it.
+pub fn check_examples(
+    cache: &InputCache,
+    solver: &dyn Solver,
+    day: u8,
+    part: Part,
+) -> Result<Vec<ExampleCheck>, Error> {
+    let mut checks = Vec::new();
+    for name in cache.examples(day)? {
+        let Some(expected) = cache.expected(day, &name)? else {
+            continue;
+        };
+        let Some(expected) = expected.get(part) else {
+            continue;
+        };
+        let input = cache.open(day, &InputKind::Example(name.clone()))?;
+        checks.push(ExampleCheck {
+            name,
+            expected: expected.to_string(),
+            actual: solver.solve(part, input).map_err(|error| error.to_string()),
+        });
+    }
+    Ok(checks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_expected_answers_of_either_part() {
+        let expected = Expected::from_toml("part_1 = 24\n").unwrap();
+        assert_eq!(expected.get(Part::One), Some("24"));
+        assert_eq!(expected.get(Part::Two), None);
+        assert!(Expected::from_toml("part_3 = 1\n").is_err());
+        assert!(Expected::from_toml("part_1 = true\n").is_err());
+    }
+
+    /// Solves every example of a day that has a sidecar, and fails with the
+    /// name of each example that came out wrong, not only the first one.
+    /// Adding an example with a sidecar is all it takes to have it checked.
+    fn check_all_examples(solver: &dyn Solver, day: u8, part: Part) {
+        let checks = check_examples(&InputCache::default(), solver, day, part).unwrap();
+        let failures: Vec<String> = checks
+            .iter()
+            .filter(|check| !check.passed())
+            .map(|check| match &check.actual {
+                Ok(actual) => format!("{}: expected {}, got {actual}", check.name, check.expected),
+                Err(error) => format!(
+                    "{}: expected {}, failed: {error}",
+                    check.name, check.expected
+                ),
+            })
+            .collect();
+        assert!(
+            failures.is_empty(),
+            "day {day} part {part}:\n{}",
+            failures.join("\n")
+        );
+    }
+
+    test_every_day!(check_all_examples);
+}
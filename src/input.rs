@@ -0,0 +1,280 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+/// Where an [`Input`] is read from, kept around so errors can point at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    Path(PathBuf),
+    Stdin,
+    Memory,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Path(path) => write!(f, "{}", path.display()),
+            Origin::Stdin => write!(f, "<stdin>"),
+            Origin::Memory => write!(f, "<memory>"),
+        }
+    }
+}
+
+/// Puzzle input handed to a [`Solver`](crate::Solver).
+///
+/// The input is streamed rather than read up front, so solutions that only
+/// need one line at a time never hold the whole file in memory.
+pub struct Input {
+    origin: Origin,
+    reader: Box<dyn BufRead>,
+}
+
+impl Input {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let origin = Origin::Path(path.to_path_buf());
+        match File::open(path) {
+            Ok(file) => Ok(Input {
+                origin,
+                reader: Box::new(BufReader::new(file)),
+            }),
+            Err(source) => Err(Error::Io { origin, source }),
+        }
+    }
+
+    pub fn from_stdin() -> Self {
+        Input {
+            origin: Origin::Stdin,
+            reader: Box::new(io::stdin().lock()),
+        }
+    }
+
+    pub fn from_text(text: &str) -> Self {
+        Input {
+            origin: Origin::Memory,
+            reader: Box::new(Cursor::new(text.as_bytes().to_vec())),
+        }
+    }
+
+    /// Reads from any buffered reader, like input generated on the fly.
+    pub fn from_reader(reader: impl BufRead + 'static) -> Self {
+        Input {
+            origin: Origin::Memory,
+            reader: Box::new(reader),
+        }
+    }
+
+    /// Opens `source` the way the command line refers to inputs: `-` means
+    /// stdin, anything else is a path.
+    pub fn open(source: &str) -> Result<Self, Error> {
+        match source {
+            "-" => Ok(Input::from_stdin()),
+            path => Input::from_path(path),
+        }
+    }
+
+    pub fn origin(&self) -> &Origin {
+        &self.origin
+    }
+
+    /// Reads whatever is left of the input into memory, so that solving it
+    /// afterwards no longer waits on the file system.
+    pub fn buffered(mut self) -> Result<Self, Error> {
+        let mut bytes = Vec::new();
+        match self.reader.read_to_end(&mut bytes) {
+            Ok(_) => Ok(Input {
+                origin: self.origin,
+                reader: Box::new(Cursor::new(bytes)),
+            }),
+            Err(source) => Err(Error::Io {
+                origin: self.origin,
+                source,
+            }),
+        }
+    }
+
+    /// Reads whatever is left of the input as is, line endings included.
+    pub fn read_to_string(mut self) -> Result<String, Error> {
+        let mut text = String::new();
+        match self.reader.read_to_string(&mut text) {
+            Ok(_) => Ok(text),
+            Err(source) => Err(Error::Io {
+                origin: self.origin,
+                source,
+            }),
+        }
+    }
+
+    /// Lines of the input without their line endings.
+    pub fn lines(self) -> Lines {
+        Lines {
+            origin: self.origin,
+            inner: self.reader.lines(),
+        }
+    }
+
+    /// Groups of lines separated by blank lines, for inputs written in
+    /// paragraphs.
+    pub fn groups(self) -> Groups {
+        Groups {
+            lines: self.lines().enumerate(),
+        }
+    }
+}
+
+/// Iterator returned by [`Input::lines`].
+pub struct Lines {
+    origin: Origin,
+    inner: io::Lines<Box<dyn BufRead>>,
+}
+
+impl Iterator for Lines {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|line| {
+            line.map_err(|source| Error::Io {
+                origin: self.origin.clone(),
+                source,
+            })
+        })
+    }
+}
+
+/// Consecutive non-blank lines of an input, see [`Input::groups`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// 0-based index of the first line of the group within the input.
+    pub first_line_index: usize,
+    pub lines: Vec<String>,
+}
+
+impl Group {
+    /// Lines of the group with their 0-based index within the input, for
+    /// pointing parse errors at the right line.
+    pub fn indexed_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(offset, line)| (self.first_line_index + offset, line.as_str()))
+    }
+}
+
+/// Iterator returned by [`Input::groups`].
+///
+/// Any number of blank lines separate two groups, and blank lines at the start
+/// or end of the input make no empty groups, so the last group comes out
+/// whether or not the input ends in a blank line. A line holding only a
+/// carriage return, as left over from CRLF line endings, counts as blank.
+pub struct Groups {
+    lines: std::iter::Enumerate<Lines>,
+}
+
+impl Iterator for Groups {
+    type Item = Result<Group, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut group: Option<Group> = None;
+        for (line_index, line) in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error)),
+            };
+            if line.trim_end_matches('\r').is_empty() {
+                match group {
+                    Some(group) => return Some(Ok(group)),
+                    None => continue,
+                }
+            }
+            group
+                .get_or_insert_with(|| Group {
+                    first_line_index: line_index,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(line);
+        }
+        group.map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_read_lines_from_text() {
+        let input = Input::from_text("1000\n2000\n\n3000\n");
+        let lines: Vec<String> = input.lines().map(Result::unwrap).collect();
+        assert_eq!(lines, vec!["1000", "2000", "", "3000"]);
+    }
+
+    fn groups(text: &str) -> Vec<Group> {
+        Input::from_text(text)
+            .groups()
+            .map(Result::unwrap)
+            .collect()
+    }
+
+    #[test]
+    fn should_split_input_into_groups() {
+        let expected = vec![
+            Group {
+                first_line_index: 0,
+                lines: vec!["1000".to_string(), "2000".to_string()],
+            },
+            Group {
+                first_line_index: 3,
+                lines: vec!["3000".to_string()],
+            },
+        ];
+        assert_eq!(groups("1000\n2000\n\n3000\n"), expected);
+        assert_eq!(groups("1000\n2000\n\n3000"), expected);
+        assert_eq!(groups("1000\r\n2000\r\n\r\n3000\r\n"), expected);
+    }
+
+    #[test]
+    fn should_not_make_empty_groups() {
+        let groups = groups("\n\n1\n\n\n\n2\n\n\n");
+        let indexes: Vec<(usize, &str)> = groups.iter().flat_map(Group::indexed_lines).collect();
+        assert_eq!(indexes, vec![(2, "1"), (6, "2")]);
+        assert!(Input::from_text("").groups().next().is_none());
+        assert!(Input::from_text("\n\n").groups().next().is_none());
+    }
+
+    #[test]
+    fn should_read_lines_from_path() {
+        let input = Input::from_path("./inputs/day_4/mocks/example.txt").unwrap();
+        assert_eq!(
+            input.origin(),
+            &Origin::Path(PathBuf::from("./inputs/day_4/mocks/example.txt"))
+        );
+        assert_eq!(input.lines().count(), 6);
+    }
+
+    #[test]
+    fn should_keep_origin_when_buffered() {
+        let input = Input::from_path("./inputs/day_4/mocks/example.txt")
+            .unwrap()
+            .buffered()
+            .unwrap();
+        assert!(matches!(input.origin(), Origin::Path(_)));
+        assert_eq!(input.lines().count(), 6);
+    }
+
+    #[test]
+    fn should_report_path_that_failed_to_open() {
+        let result = Input::open("./inputs/day_0/missing.txt");
+        match result {
+            Err(error) => assert!(error
+                .to_string()
+                .starts_with("./inputs/day_0/missing.txt: ")),
+            Ok(_) => panic!("missing file should not open"),
+        }
+    }
+}
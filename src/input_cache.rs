@@ -0,0 +1,287 @@
+//! Keeps track of the inputs stored under `inputs/`.
+//!
+//! Every day has a directory of its own, laid out as:
+//!
+//! ```text
+//! inputs/
+//! ├── answers.toml          accepted answers, see `crate::answers`
+//! └── day_N/
+//!     ├── aoc_input.txt     my puzzle input
+//!     └── mocks/
+//!         ├── example.txt   examples, named after their path below `mocks/`
+//!         └── first_part/
+//!             └── win.txt   ... so this one is the example `first_part/win`
+//! ```
+//!
+//! Inputs imported through [`InputCache::import`] are normalised to `\n` line
+//! endings with exactly one trailing newline, the way the puzzle page serves
+//! them.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{input::Origin, Error, Input};
+
+/// Directory the inputs live in, relative to the root of the crate.
+pub const INPUTS_DIR: &str = "./inputs";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputKind {
+    /// My real puzzle input.
+    Puzzle,
+    /// A hand-made or puzzle-provided example, named after its path below
+    /// `mocks/` without the `.txt` extension.
+    Example(String),
+}
+
+impl fmt::Display for InputKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputKind::Puzzle => write!(f, "puzzle input"),
+            InputKind::Example(name) => write!(f, "example {name}"),
+        }
+    }
+}
+
+/// What is stored for one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayInputs {
+    pub day: u8,
+    pub has_puzzle_input: bool,
+    pub examples: Vec<String>,
+}
+
+/// What [`normalize`] had to change to get an input into shape.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Normalized {
+    pub text: String,
+    /// Lines that ended in `\r\n`.
+    pub crlf_line_endings: usize,
+    pub added_trailing_newline: bool,
+    /// Blank lines dropped from the end of the input.
+    pub removed_trailing_blank_lines: usize,
+}
+
+/// Gets `text` into the shape inputs are stored in: `\n` line endings and
+/// exactly one newline at the end. Empty inputs, and carriage returns that are
+/// not part of a line ending, are rejected.
+pub fn normalize(text: &str) -> Result<Normalized, String> {
+    let crlf_line_endings = text.matches("\r\n").count();
+    let mut normalized = text.replace("\r\n", "\n");
+
+    if let Some(line_index) = normalized.lines().position(|line| line.contains('\r')) {
+        return Err(format!(
+            "line {} has a carriage return that is not part of a line ending",
+            line_index + 1
+        ));
+    }
+
+    let content_length = normalized.trim_end_matches('\n').len();
+    if content_length == 0 {
+        return Err("input is empty".to_string());
+    }
+    let trailing_newlines = normalized.len() - content_length;
+    normalized.truncate(content_length);
+    normalized.push('\n');
+
+    Ok(Normalized {
+        text: normalized,
+        crlf_line_endings,
+        added_trailing_newline: trailing_newlines == 0,
+        removed_trailing_blank_lines: trailing_newlines.saturating_sub(1),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCache {
+    root: PathBuf,
+}
+
+impl Default for InputCache {
+    fn default() -> Self {
+        InputCache::new(INPUTS_DIR)
+    }
+}
+
+impl InputCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputCache { root: root.into() }
+    }
+
+    pub fn day_dir(&self, day: u8) -> PathBuf {
+        self.root.join(format!("day_{day}"))
+    }
+
+    pub fn path(&self, day: u8, kind: &InputKind) -> PathBuf {
+        match kind {
+            InputKind::Puzzle => self.day_dir(day).join("aoc_input.txt"),
+            InputKind::Example(name) => self.day_dir(day).join("mocks").join(format!("{name}.txt")),
+        }
+    }
+
+    pub fn exists(&self, day: u8, kind: &InputKind) -> bool {
+        self.path(day, kind).is_file()
+    }
+
+    pub fn open(&self, day: u8, kind: &InputKind) -> Result<Input, Error> {
+        Input::from_path(self.path(day, kind))
+    }
+
+    /// Stores `source` as the `kind` input of `day`, replacing whatever was
+    /// there, and reports what had to be normalised on the way.
+    pub fn import(&self, day: u8, kind: &InputKind, source: Input) -> Result<Normalized, Error> {
+        let origin = source.origin().clone();
+        let text = source.read_to_string()?;
+        let normalized =
+            normalize(&text).map_err(|message| Error::InvalidFile { origin, message })?;
+
+        let path = self.path(day, kind);
+        let io_error = |source| Error::Io {
+            origin: Origin::Path(path.clone()),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        fs::write(&path, &normalized.text).map_err(io_error)?;
+        Ok(normalized)
+    }
+
+    /// Names of the examples stored for `day`, sorted.
+    pub fn examples(&self, day: u8) -> Result<Vec<String>, Error> {
+        let mocks = self.day_dir(day).join("mocks");
+        let mut examples = Vec::new();
+        if mocks.is_dir() {
+            collect_examples(&mocks, &mocks, &mut examples)?;
+        }
+        examples.sort();
+        Ok(examples)
+    }
+
+    /// What is stored for every day of the calendar that has anything at all.
+    pub fn list(&self) -> Result<Vec<DayInputs>, Error> {
+        let mut days = Vec::new();
+        for day in 1..=25 {
+            let day_inputs = DayInputs {
+                day,
+                has_puzzle_input: self.exists(day, &InputKind::Puzzle),
+                examples: self.examples(day)?,
+            };
+            if day_inputs.has_puzzle_input || !day_inputs.examples.is_empty() {
+                days.push(day_inputs);
+            }
+        }
+        Ok(days)
+    }
+}
+
+fn collect_examples(mocks: &Path, dir: &Path, examples: &mut Vec<String>) -> Result<(), Error> {
+    let io_error = |source| Error::Io {
+        origin: Origin::Path(dir.to_path_buf()),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            collect_examples(mocks, &path, examples)?;
+        } else if path.extension().is_some_and(|extension| extension == "txt") {
+            let name = path.strip_prefix(mocks).unwrap_or(&path).with_extension("");
+            let name = name
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            examples.push(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> InputCache {
+        let root = std::env::temp_dir().join(format!("aoc_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        InputCache::new(root)
+    }
+
+    #[test]
+    fn should_resolve_documented_layout() {
+        let cache = InputCache::new("./inputs");
+        assert_eq!(
+            cache.path(1, &InputKind::Puzzle),
+            PathBuf::from("./inputs/day_1/aoc_input.txt")
+        );
+        assert_eq!(
+            cache.path(2, &InputKind::Example("first_part/win".to_string())),
+            PathBuf::from("./inputs/day_2/mocks/first_part/win.txt")
+        );
+    }
+
+    #[test]
+    fn should_list_examples_of_existing_days() {
+        let cache = InputCache::default();
+        assert_eq!(
+            cache.examples(2).unwrap(),
+            vec![
+                "example",
+                "first_part/draw",
+                "first_part/lose",
+                "first_part/win",
+                "second_part/draw",
+                "second_part/lose",
+                "second_part/win",
+            ]
+        );
+        assert!(cache.examples(25).unwrap().is_empty());
+        assert!(cache.list().unwrap().iter().any(|day| day.day == 5));
+    }
+
+    #[test]
+    fn should_normalize_line_endings_and_trailing_newlines() {
+        let normalized = normalize("1000\r\n2000\r\n\r\n3000").unwrap();
+        assert_eq!(normalized.text, "1000\n2000\n\n3000\n");
+        assert_eq!(normalized.crlf_line_endings, 3);
+        assert!(normalized.added_trailing_newline);
+
+        let normalized = normalize("A Y\n\n\n").unwrap();
+        assert_eq!(normalized.text, "A Y\n");
+        assert_eq!(normalized.removed_trailing_blank_lines, 2);
+        assert!(!normalized.added_trailing_newline);
+    }
+
+    #[test]
+    fn should_reject_empty_input_and_stray_carriage_returns() {
+        assert!(normalize("\n\n").is_err());
+        assert!(normalize("A Y\rB X\n").is_err());
+    }
+
+    #[test]
+    fn should_import_into_cache() {
+        let cache = temp_cache("import");
+        let report = cache
+            .import(3, &InputKind::Puzzle, Input::from_text("abcd\r\nefgh"))
+            .unwrap();
+        assert_eq!(report.crlf_line_endings, 1);
+        assert_eq!(
+            fs::read_to_string(cache.path(3, &InputKind::Puzzle)).unwrap(),
+            "abcd\nefgh\n"
+        );
+
+        let example = InputKind::Example("small".to_string());
+        cache.import(3, &example, Input::from_text("ab\n")).unwrap();
+        assert_eq!(
+            cache.list().unwrap(),
+            vec![DayInputs {
+                day: 3,
+                has_puzzle_input: true,
+                examples: vec!["small".to_string()],
+            }]
+        );
+        fs::remove_dir_all(&cache.root).unwrap();
+    }
+}
@@ -1,5 +1,48 @@
-use std::{fs::File, io::BufReader};
+use std::{any::Any, fmt, str::FromStr};
 
+/// Generates a test module for every day, `day_1` to `day_25`, with a
+/// `part_one` and a `part_two` test calling `$check(solver, day, part)`, so
+/// each day and part passes or fails on its own. Days without a registered
+/// solution pass without calling it.
+#[cfg(test)]
+macro_rules! test_every_day {
+    ($check:ident) => {
+        test_every_day! {
+            @days $check;
+            day_1 => 1, day_2 => 2, day_3 => 3, day_4 => 4, day_5 => 5,
+            day_6 => 6, day_7 => 7, day_8 => 8, day_9 => 9, day_10 => 10,
+            day_11 => 11, day_12 => 12, day_13 => 13, day_14 => 14, day_15 => 15,
+            day_16 => 16, day_17 => 17, day_18 => 18, day_19 => 19, day_20 => 20,
+            day_21 => 21, day_22 => 22, day_23 => 23, day_24 => 24, day_25 => 25,
+        }
+    };
+    (@days $check:ident; $($module:ident => $day:literal),* $(,)?) => {
+        $(
+            mod $module {
+                use crate::Part;
+
+                fn check(part: Part) {
+                    if let Some(solver) = crate::solver($day) {
+                        super::$check(solver, $day, part);
+                    }
+                }
+
+                #[test]
+                fn part_one() {
+                    check(Part::One);
+                }
+
+                #[test]
+                fn part_two() {
+                    check(Part::Two);
+                }
+            }
+        )*
+    };
+}
+
+pub mod answers;
+pub mod bench;
 pub mod day_1;
 pub mod day_2;
 pub mod day_3;
@@ -8,8 +51,162 @@ pub mod day_5;
 pub mod day_6;
 pub mod day_7;
 pub mod day_8;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod input_cache;
+pub mod log;
+pub mod mode;
+
+pub use error::{Error, ParseError};
+pub use input::Input;
+pub use mode::{Mode, Options};
+
+/// Every puzzle day comes in two parts, the second one unlocking after the
+/// first is solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("part should be 1 or 2, got {s:?}")),
+        }
+    }
+}
+
+/// Shared entry point of a day, so that tooling can run any solution without
+/// knowing the name of the function that actually solves it.
+///
+/// Both parts consume the puzzle input, and hand back the answer formatted the
+/// way the puzzle page expects it.
+/// What [`Solver::parse`] made of an input, for [`Solver::solve_parsed`] to
+/// downcast to the type it knows it is.
+pub type Parsed = Box<dyn Any>;
+
+pub trait Solver {
+    fn part_one(&self, input: Input) -> Result<String, Error>;
+    fn part_two(&self, input: Input) -> Result<String, Error>;
+
+    fn solve(&self, part: Part, input: Input) -> Result<String, Error> {
+        self.solve_parsed(part, self.parse(input)?)
+    }
+
+    /// Parses `input` into what both parts solve from, so that parsing can be
+    /// timed on its own. Solvers that parse as they go keep the default, which
+    /// hands the input on as it is.
+    fn parse(&self, input: Input) -> Result<Parsed, Error> {
+        Ok(Box::new(input))
+    }
+
+    /// Solves `part` from what [`parse`](Solver::parse) made of the input.
+    /// Overridden together with `parse`.
+    fn solve_parsed(&self, part: Part, parsed: Parsed) -> Result<String, Error> {
+        let input = *parsed
+            .downcast::<Input>()
+            .expect("the default parse hands the input on");
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    /// Extra modes the day offers besides its two parts, none by default.
+    fn modes(&self) -> &'static [Mode] {
+        &[]
+    }
+
+    /// Runs one of the [`modes`](Solver::modes) against `input`.
+    fn run_mode(&self, mode: &str, options: &Options, input: Input) -> Result<String, Error> {
+        let _ = (options, input);
+        Err(mode::unknown(mode, self.modes()))
+    }
+}
+
+/// Days that have both parts solved. Day 7 and onwards are still being worked
+/// on, so they are left out until they produce real answers.
+const REGISTRY: [(u8, &dyn Solver); 6] = [
+    (1, &day_1::Day1),
+    (2, &day_2::Day2),
+    (3, &day_3::Day3),
+    (4, &day_4::Day4),
+    (5, &day_5::Day5),
+    (6, &day_6::Day6),
+];
+
+/// Looks up the solver registered for `day`.
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    REGISTRY
+        .iter()
+        .find(|(registered_day, _)| *registered_day == day)
+        .map(|(_, solver)| *solver)
+}
+
+/// Every registered `(day, part)` pair, ordered by day and then part.
+pub fn registered() -> impl Iterator<Item = (u8, Part)> {
+    REGISTRY
+        .iter()
+        .flat_map(|(day, _)| Part::ALL.into_iter().map(move |part| (*day, part)))
+}
+
+/// Runs the solution for `day` and `part` against `input`.
+pub fn solve(day: u8, part: Part, input: Input) -> Result<String, Error> {
+    match solver(day) {
+        Some(solver) => solver.solve(part, input),
+        None => Err(Error::UnknownDay(day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_list_both_parts_of_every_registered_day() {
+        let entries: Vec<(u8, Part)> = registered().collect();
+        assert_eq!(entries.len(), REGISTRY.len() * 2);
+        assert_eq!(entries.first(), Some(&(1, Part::One)));
+        assert_eq!(entries.get(1), Some(&(1, Part::Two)));
+    }
+
+    #[test]
+    fn should_parse_part_from_cli_value() {
+        assert_eq!(Part::from_str("1"), Ok(Part::One));
+        assert_eq!(Part::from_str("two"), Ok(Part::Two));
+        assert!(Part::from_str("3").is_err());
+    }
+
+    #[test]
+    fn should_not_find_unregistered_day() {
+        assert!(solver(25).is_none());
+        let result = solve(25, Part::One, Input::from_text(""));
+        assert!(matches!(result, Err(Error::UnknownDay(25))));
+    }
 
-pub fn read_file(filename: &str) -> BufReader<File> {
-    let file = File::open(filename).unwrap();
-    BufReader::new(file)
+    #[test]
+    fn should_solve_through_registry() {
+        let input = Input::from_path("./inputs/day_1/mocks/asc.txt").unwrap();
+        let result = solve(1, Part::One, input).unwrap();
+        assert_eq!(result, "50");
+    }
 }
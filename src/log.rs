@@ -0,0 +1,144 @@
+//! Tiny leveled logger so solvers can explain what they are doing without
+//! getting in the way of the answer.
+//!
+//! Messages go to stderr, and nothing is printed until a level is picked with
+//! [`set_level`], either from the `AOC_LOG` environment variable or from the
+//! `--log` flag of the binary.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Environment variable read by [`init_from_env`].
+pub const ENV_VAR: &str = "AOC_LOG";
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+/// How chatty the solvers are, each level including the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off = 0,
+    /// Milestones of a run, like the final state a puzzle ends up in.
+    Info,
+    /// Decisions made per record of the input.
+    Debug,
+    /// Every step taken, which can mean several lines per line of input.
+    Trace,
+}
+
+impl Level {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Level::Info,
+            2 => Level::Debug,
+            3 => Level::Trace,
+            _ => Level::Off,
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "off" => Ok(Level::Off),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "log level should be one of off, info, debug, trace, got {s:?}"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Off => write!(f, "off"),
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    Level::from_u8(LEVEL.load(Ordering::Relaxed))
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Off && level <= self::level()
+}
+
+/// Picks the level from [`ENV_VAR`], leaving logging off when it is not set.
+pub fn init_from_env() -> Result<(), String> {
+    match std::env::var(ENV_VAR) {
+        Ok(value) => {
+            set_level(Level::from_str(&value)?);
+            Ok(())
+        }
+        Err(_) => Ok(()),
+    }
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, message: fmt::Arguments<'_>) {
+    eprintln!("[{level}] {message}");
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Info) {
+            $crate::log::write($crate::log::Level::Info, format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_level_regardless_of_case() {
+        assert_eq!(Level::from_str("off"), Ok(Level::Off));
+        assert_eq!(Level::from_str("INFO"), Ok(Level::Info));
+        assert_eq!(Level::from_str("Debug"), Ok(Level::Debug));
+        assert_eq!(Level::from_str("trace"), Ok(Level::Trace));
+        assert!(Level::from_str("loud").is_err());
+    }
+
+    #[test]
+    fn should_order_levels_from_quiet_to_chatty() {
+        assert!(Level::Off < Level::Info);
+        assert!(Level::Info < Level::Debug);
+        assert!(Level::Debug < Level::Trace);
+        assert_eq!(Level::from_u8(Level::Trace as u8), Level::Trace);
+    }
+}
@@ -0,0 +1,390 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use aoc::{
+    answers::{self, Answers, Verdict, Verification},
+    bench::{self, BenchOptions, Measurement, Timing},
+    input_cache::{InputCache, InputKind},
+    log, mode, Error, Input, Options, Part,
+};
+use clap::{Args, Parser, Subcommand};
+
+/// Runs the Advent of Code 2022 solutions of this crate.
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    /// How much the solvers log to stderr: off, info, debug or trace.
+    /// Overrides the `AOC_LOG` environment variable.
+    #[arg(long, global = true)]
+    log: Option<log::Level>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solves one part of a day, or runs one of its modes, and prints the
+    /// answer.
+    Run(RunArgs),
+    /// Manages the inputs stored under `inputs/`.
+    #[command(subcommand)]
+    Inputs(InputsCommand),
+    /// Times every registered day and part against its puzzle input.
+    Bench(BenchArgs),
+    /// Checks every registered day and part against the stored answers.
+    Verify {
+        /// Only check this day.
+        #[arg(long)]
+        day: Option<u8>,
+        #[arg(long, default_value = answers::ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+    /// Solves every registered day and part that has a puzzle input, and
+    /// prints a summary table with the verification status of each.
+    All {
+        #[arg(long, default_value = answers::ANSWERS_PATH)]
+        answers: PathBuf,
+    },
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    #[arg(long)]
+    day: u8,
+    /// Either `1` or `2`.
+    #[arg(long, required_unless_present_any = ["mode", "list_modes"])]
+    part: Option<Part>,
+    /// Runs a mode of the day instead of one of its parts, see `--list-modes`.
+    #[arg(long, conflicts_with = "part")]
+    mode: Option<String>,
+    /// Option for the mode, as `key=value`. Can be repeated.
+    #[arg(long = "opt", requires = "mode", conflicts_with = "part", value_parser = mode::parse_option)]
+    options: Vec<(String, String)>,
+    /// Lists the modes of the day instead of running anything.
+    #[arg(long, conflicts_with_all = ["part", "mode", "input", "example"])]
+    list_modes: bool,
+    /// Path to the puzzle input, or `-` to read it from stdin. Defaults
+    /// to the puzzle input stored for the day.
+    #[arg(long, conflicts_with = "example")]
+    input: Option<String>,
+    /// Solve a stored example instead, e.g. `first_part/win`.
+    #[arg(long)]
+    example: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum InputsCommand {
+    /// Lists the days that have a puzzle input or examples stored.
+    List,
+    /// Prints where an input of a day is stored.
+    Path(InputArgs),
+    /// Stores an input, normalising its line endings on the way.
+    Import {
+        #[command(flatten)]
+        input: InputArgs,
+        /// File to import, or `-` to read it from stdin.
+        #[arg(long)]
+        from: String,
+    },
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    #[arg(long)]
+    day: u8,
+    /// Name of an example, e.g. `first_part/win`, instead of the puzzle input.
+    #[arg(long)]
+    example: Option<String>,
+}
+
+impl InputArgs {
+    fn kind(&self) -> InputKind {
+        match &self.example {
+            Some(name) => InputKind::Example(name.clone()),
+            None => InputKind::Puzzle,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Only time this day.
+    #[arg(long)]
+    day: Option<u8>,
+    /// Only time this part.
+    #[arg(long)]
+    part: Option<Part>,
+    /// Timed runs per day and part.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// Untimed runs made before the timed ones.
+    #[arg(long, default_value_t = 2)]
+    warm_up: u32,
+    /// Print the results as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+    /// Store the results as JSON, to compare a later run against.
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// Results stored by an earlier `--save` to compare against.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Err(error) = log::init_from_env() {
+        eprintln!("{}: {error}", log::ENV_VAR);
+        return ExitCode::FAILURE;
+    }
+    if let Some(level) = cli.log {
+        log::set_level(level);
+    }
+
+    let result = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Inputs(command) => run_inputs(command),
+        Command::Bench(args) => run_bench(args),
+        Command::Verify { day, answers } => run_verify(day, &answers),
+        Command::All { answers } => run_all(&answers),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: RunArgs) -> Result<ExitCode, Error> {
+    let day = args.day;
+    let solver = aoc::solver(day).ok_or(Error::UnknownDay(day))?;
+    if args.list_modes {
+        for mode in solver.modes() {
+            println!("{:<12} {}", mode.name, mode.about);
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let input = match (args.input, args.example) {
+        (Some(source), _) => Input::open(&source)?,
+        (None, Some(name)) => InputCache::default().open(day, &InputKind::Example(name))?,
+        (None, None) => InputCache::default().open(day, &InputKind::Puzzle)?,
+    };
+    let answer = match (args.mode, args.part) {
+        (Some(mode), _) => {
+            let options: Options = args.options.into_iter().collect();
+            solver.run_mode(&mode, &options, input)?
+        }
+        (None, Some(part)) => solver.solve(part, input)?,
+        (None, None) => unreachable!("clap requires a part unless a mode is given"),
+    };
+    println!("{answer}");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_inputs(command: InputsCommand) -> Result<ExitCode, Error> {
+    let cache = InputCache::default();
+    match command {
+        InputsCommand::List => {
+            for day in cache.list()? {
+                let puzzle = match day.has_puzzle_input {
+                    true => "puzzle input",
+                    false => "no puzzle input",
+                };
+                println!(
+                    "day {:>2}: {puzzle:<16} {} example(s) {}",
+                    day.day,
+                    day.examples.len(),
+                    day.examples.join(", ")
+                );
+            }
+        }
+        InputsCommand::Path(input) => {
+            println!("{}", cache.path(input.day, &input.kind()).display());
+        }
+        InputsCommand::Import { input, from } => {
+            let kind = input.kind();
+            let normalized = cache.import(input.day, &kind, Input::open(&from)?)?;
+            if normalized.crlf_line_endings > 0 {
+                eprintln!(
+                    "converted {} CRLF line ending(s)",
+                    normalized.crlf_line_endings
+                );
+            }
+            if normalized.added_trailing_newline {
+                eprintln!("added the missing trailing newline");
+            }
+            if normalized.removed_trailing_blank_lines > 0 {
+                eprintln!(
+                    "removed {} trailing blank line(s)",
+                    normalized.removed_trailing_blank_lines
+                );
+            }
+            println!(
+                "stored {kind} of day {} at {}",
+                input.day,
+                cache.path(input.day, &kind).display()
+            );
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_bench(args: BenchArgs) -> Result<ExitCode, Error> {
+    let options = BenchOptions {
+        iterations: args.iterations,
+        warm_up: args.warm_up,
+    };
+    let measurements = bench::bench_registered(args.day, args.part, options)?;
+    let baseline = match &args.baseline {
+        Some(path) => bench::load(path)?,
+        None => Vec::new(),
+    };
+
+    if args.json {
+        println!("{}", bench::to_json(&measurements));
+    } else {
+        print_bench_table(&measurements, &baseline);
+    }
+    if let Some(path) = &args.save {
+        bench::save(&measurements, path)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Fails when any answer differs from the stored one, or a solution fails.
+/// Parts that could not be checked are listed, but do not fail the run.
+fn run_verify(day: Option<u8>, answers_path: &Path) -> Result<ExitCode, Error> {
+    let answers = Answers::load(answers_path)?;
+    let verifications = answers::verify_registered(&answers, day)?;
+
+    for verification in &verifications {
+        println!(
+            "day {:>2} part {}: {:<16} {}",
+            verification.day,
+            verification.part,
+            verification.answer.as_deref().unwrap_or_default(),
+            describe(&verification.verdict)
+        );
+    }
+    Ok(exit_code(&verifications))
+}
+
+/// Smoke check of every day and part of the calendar in one go, listing days
+/// without a solution too. Unlike `verify`, a missing answers file only leaves
+/// the answers unverified.
+fn run_all(answers_path: &Path) -> Result<ExitCode, Error> {
+    let answers = match answers_path.exists() {
+        true => Answers::load(answers_path)?,
+        false => Answers::default(),
+    };
+    let verifications = answers::verify_every_day(&answers);
+
+    println!(
+        "{:>3} {:>4}  {:<16} {:<32} {:>10}",
+        "day", "part", "answer", "status", "elapsed"
+    );
+    for verification in &verifications {
+        let elapsed = match verification.elapsed {
+            Some(elapsed) => format!("{elapsed:.1?}"),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:>3} {:>4}  {:<16} {:<32} {:>10}",
+            verification.day,
+            verification.part,
+            verification.answer.as_deref().unwrap_or("-"),
+            describe(&verification.verdict),
+            elapsed
+        );
+    }
+
+    let count = |wanted: fn(&Verdict) -> bool| {
+        verifications
+            .iter()
+            .filter(|verification| wanted(&verification.verdict))
+            .count()
+    };
+    let total: Duration = verifications
+        .iter()
+        .filter_map(|verification| verification.elapsed)
+        .sum();
+    println!(
+        "{} correct, {} wrong, {} failed, {} unverified, {} without input, {} without solution \
+         in {total:.1?}",
+        count(|verdict| matches!(verdict, Verdict::Correct)),
+        count(|verdict| matches!(verdict, Verdict::Wrong { .. })),
+        count(|verdict| matches!(verdict, Verdict::Failed { .. })),
+        count(|verdict| matches!(verdict, Verdict::Unverified)),
+        count(|verdict| matches!(verdict, Verdict::NoInput)),
+        count(|verdict| matches!(verdict, Verdict::NoSolution)),
+    );
+    Ok(exit_code(&verifications))
+}
+
+fn describe(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Correct => "correct".to_string(),
+        Verdict::Wrong { expected } => format!("WRONG, expected {expected}"),
+        Verdict::Failed { message } => format!("FAILED: {message}"),
+        Verdict::Unverified => "no stored answer".to_string(),
+        Verdict::NoInput => "no puzzle input".to_string(),
+        Verdict::NoSolution => "no solution".to_string(),
+    }
+}
+
+fn exit_code(verifications: &[Verification]) -> ExitCode {
+    match verifications
+        .iter()
+        .any(|verification| verification.verdict.is_failure())
+    {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+fn print_bench_table(measurements: &[Measurement], baseline: &[Measurement]) {
+    println!(
+        "{:>3} {:>4}  {:<12} {:<32} {:<32} {:<32} {:>8}",
+        "day",
+        "part",
+        "answer",
+        "load min / median / max",
+        "parse min / median / max",
+        "solve min / median / max",
+        "vs base"
+    );
+    for measurement in measurements {
+        let compared = match measurement.compared_to(baseline) {
+            Some(ratio) => format!("{:+.1}%", (ratio - 1.0) * 100.0),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:>3} {:>4}  {:<12} {:<32} {:<32} {:<32} {:>8}",
+            measurement.day,
+            measurement.part,
+            measurement.answer,
+            format_timing(&measurement.load),
+            format_timing(&measurement.parse),
+            format_timing(&measurement.solve),
+            compared
+        );
+    }
+}
+
+fn format_timing(timing: &Timing) -> String {
+    let format = |duration: Duration| format!("{duration:.1?}");
+    format!(
+        "{} / {} / {}",
+        format(timing.min()),
+        format(timing.median()),
+        format(timing.max())
+    )
+}
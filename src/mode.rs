@@ -0,0 +1,115 @@
+//! Extra ways a day can look at its input besides solving the two parts, like
+//! printing a report or answering a variant of the puzzle.
+//!
+//! Modes are run from the command line with
+//! `aoc run --day 1 --mode top --opt n=5`, where every `--opt` is a `key=value`
+//! pair handed to the mode as [`Options`].
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::Error;
+
+/// A mode a [`Solver`](crate::Solver) offers, as listed by the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mode {
+    pub name: &'static str,
+    /// One line on what the mode prints, and which options it takes.
+    pub about: &'static str,
+}
+
+/// `key=value` options given to a mode.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    inner: BTreeMap<String, String>,
+}
+
+impl Options {
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.inner.insert(key.into(), value.into());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.inner.get(key).map(String::as_str)
+    }
+
+    /// Parses the option `key`, `None` when it was not given.
+    pub fn parse<T>(&self, key: &str) -> Result<Option<T>, Error>
+    where
+        T: FromStr,
+        T::Err: ToString,
+    {
+        self.get(key)
+            .map(|value| {
+                value.parse().map_err(|error: T::Err| Error::InvalidOption {
+                    name: key.to_string(),
+                    message: format!("{value:?}: {}", error.to_string()),
+                })
+            })
+            .transpose()
+    }
+
+    /// Parses the option `key`, falling back to `default` when it was not
+    /// given.
+    pub fn parse_or<T>(&self, key: &str, default: T) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: ToString,
+    {
+        Ok(self.parse(key)?.unwrap_or(default))
+    }
+}
+
+impl FromIterator<(String, String)> for Options {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Options {
+            inner: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Error for a `mode` that is not among `modes`, listing the ones that are.
+pub fn unknown(mode: &str, modes: &[Mode]) -> Error {
+    Error::UnknownMode {
+        mode: mode.to_string(),
+        available: modes.iter().map(|mode| mode.name).collect(),
+    }
+}
+
+/// Reads one option written as `key=value`, the way `--opt` takes it.
+pub fn parse_option(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected an option like `key=value`, got {s:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_options_with_defaults() {
+        let options: Options = ["n=5", "format=csv"]
+            .into_iter()
+            .map(|option| parse_option(option).unwrap())
+            .collect();
+        assert_eq!(options.parse_or("n", 3).unwrap(), 5);
+        assert_eq!(options.parse_or("k", 3).unwrap(), 3);
+        assert_eq!(options.get("format"), Some("csv"));
+    }
+
+    #[test]
+    fn should_reject_malformed_options() {
+        assert!(parse_option("n").is_err());
+        assert!(parse_option("=5").is_err());
+        assert_eq!(
+            parse_option("sep=a=b"),
+            Ok(("sep".to_string(), "a=b".to_string()))
+        );
+        let options: Options = [parse_option("n=five").unwrap()].into_iter().collect();
+        assert!(matches!(
+            options.parse_or("n", 3),
+            Err(Error::InvalidOption { name, .. }) if name == "n"
+        ));
+    }
+}
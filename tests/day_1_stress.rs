@@ -0,0 +1,127 @@
+//! Runs day 1 over a few million generated lines, and checks that solving it
+//! takes no more memory than a short input does.
+//!
+//! Lives in its own test binary, since it needs a global allocator that keeps
+//! count of the bytes allocated.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    io::{BufReader, Read},
+};
+
+use aoc::{day_1::Day1, Input, Solver};
+
+/// Counts the bytes live on the current thread, and the most there were at
+/// once, so tests running alongside do not get in the way.
+struct CountingAllocator;
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(change: isize) {
+    // `try_with`, since the thread locals are gone while a thread shuts down
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + change);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Most bytes live at once while running `f`, on top of those live before.
+fn peak_bytes_during<T>(f: impl FnOnce() -> T) -> (T, isize) {
+    let before = LIVE.with(Cell::get);
+    PEAK.with(|peak| peak.set(before));
+    let result = f();
+    (result, PEAK.with(Cell::get) - before)
+}
+
+/// Writes the input of `elves` elves, each carrying three items, without ever
+/// holding more than one elf of it.
+struct GeneratedElves {
+    elves: usize,
+    next_elf: usize,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl GeneratedElves {
+    fn new(elves: usize) -> Self {
+        GeneratedElves {
+            elves,
+            next_elf: 0,
+            pending: Vec::with_capacity(64),
+            position: 0,
+        }
+    }
+
+    /// Calories of the items of elf `elf`, topping out at 9972 + 1 + 2.
+    fn items(elf: usize) -> [usize; 3] {
+        [elf % 9973, 1, 2]
+    }
+}
+
+impl Read for GeneratedElves {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.position == self.pending.len() {
+            if self.next_elf == self.elves {
+                return Ok(0);
+            }
+            self.pending.clear();
+            self.position = 0;
+            for item in GeneratedElves::items(self.next_elf) {
+                self.pending.extend_from_slice(item.to_string().as_bytes());
+                self.pending.push(b'\n');
+            }
+            self.pending.push(b'\n');
+            self.next_elf += 1;
+        }
+        let length = buf.len().min(self.pending.len() - self.position);
+        buf[..length].copy_from_slice(&self.pending[self.position..self.position + length]);
+        self.position += length;
+        Ok(length)
+    }
+}
+
+fn generated(elves: usize) -> Input {
+    Input::from_reader(BufReader::new(GeneratedElves::new(elves)))
+}
+
+#[test]
+fn should_solve_millions_of_lines_in_constant_memory() {
+    // four lines per elf
+    let elves = 500_000;
+
+    let (answer, small_peak) = peak_bytes_during(|| Day1.part_two(generated(1_000)));
+    assert_eq!(answer.unwrap(), "3003");
+
+    let (answer, large_peak) = peak_bytes_during(|| Day1.part_two(generated(elves)));
+    assert_eq!(answer.unwrap(), (3 * (9972 + 1 + 2)).to_string());
+
+    assert!(
+        large_peak <= small_peak + 1024,
+        "peak went from {small_peak} bytes for 1000 elves to {large_peak} bytes for {elves}"
+    );
+}
+
+#[test]
+fn should_find_largest_elf_of_millions_of_lines() {
+    let answer = Day1.part_one(generated(750_000)).unwrap();
+    assert_eq!(answer, (9972 + 1 + 2).to_string());
+}